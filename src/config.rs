@@ -7,6 +7,8 @@ pub struct Config {
     pub server_port: u16,
     /// GDB command execution timeout in seconds
     pub command_timeout: u64,
+    /// Number of stream output entries kept per session
+    pub output_buffer_size: usize,
 }
 
 impl Default for Config {
@@ -21,6 +23,10 @@ impl Default for Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(10),
+            output_buffer_size: std::env::var("GDB_OUTPUT_BUFFER_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1000),
         }
     }
 }
@@ -1,9 +1,14 @@
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use tokio::sync::{Mutex, mpsc};
+use serde_json::{Value, json};
+use tokio::process::Child;
+use tokio::sync::{Mutex, broadcast, mpsc};
 use tokio::task::JoinHandle;
 use tracing::{debug, error, warn};
 use uuid::Uuid;
@@ -11,32 +16,89 @@ use uuid::Uuid;
 use crate::TRANSPORT;
 use crate::config::Config;
 use crate::error::{AppError, AppResult};
-use crate::mi::commands::{BreakPointLocation, BreakPointNumber, MiCommand, RegisterFormat};
-use crate::mi::output::{OutOfBandRecord, ResultClass, ResultRecord};
+use crate::mi::commands::{
+    BreakPointLocation, BreakPointNumber, BreakPointOptions, CatchEvent, DisassembleMode,
+    MiCommand, RecordMethod, RegisterFormat, VarFormat, WatchMode,
+};
+use crate::mi::output::{
+    AsyncClass, OutOfBandRecord, RecordEvent, ResultClass, ResultRecord, ThreadEvent,
+};
 use crate::mi::{GDB, GDBBuilder};
 use crate::models::{
-    BreakPoint, GDBSession, GDBSessionStatus, Memory, Register, StackFrame, Variable,
+    Address, AsmInstruction, BreakPoint, CoreReport, Disassembly, Evaluation, FrameArguments,
+    GDBSession, GDBSessionStatus, Memory, MemoryMapping, MiResult, OutputBuffer, OutputPage,
+    PrintValue, ProcessInfo, RecordStatus, Register, RegisterChange, RegisterInfo, RegisterRaw,
+    RemoteTarget, SignalHandling, StackFrame, StackFrames, StopEvent, StopReason, ThreadBacktrace,
+    ThreadList, VarChange, VarChildren, VarObject, Variable, WatchPoint, parse_architecture,
+    parse_asm_insns, parse_catchpoint, parse_info_registers, parse_memory_mappings,
+    parse_record_status, parse_register_groups, parse_signal_handling, parse_terminating_signal,
 };
 
+tokio::task_local! {
+    /// Console text of the MI commands sent within `collect_console`
+    static CONSOLE: RefCell<String>;
+}
+
+/// Run the commands of a future and return its output with the console text
+/// GDB printed for its MI commands, the console text of CLI commands is their
+/// output
+pub async fn collect_console<F: Future>(future: F) -> (F::Output, String) {
+    CONSOLE
+        .scope(RefCell::new(String::new()), async {
+            let output = future.await;
+            (output, CONSOLE.with(|console| console.take()))
+        })
+        .await
+}
+
 /// GDB Session Manager
 #[derive(Default)]
 pub struct GDBManager {
     /// Configuration
     config: Config,
-    /// Session mapping table
-    sessions: Mutex<HashMap<String, GDBSessionHandle>>,
+    /// Session mapping table, only locked to look up, insert or remove sessions
+    sessions: Mutex<HashMap<String, Arc<GDBSessionHandle>>>,
 }
 
 /// GDB Session Handle
 struct GDBSessionHandle {
-    /// Session information
-    info: GDBSession,
+    /// Session information, shared with the OOB task which tracks the status
+    info: Arc<Mutex<GDBSession>>,
     /// GDB instance
-    gdb: GDB,
+    gdb: Mutex<GDB>,
+    /// GDB process, shared with the GDB instance so that it can still be killed
+    /// while a command is pending
+    process: Arc<Mutex<Child>>,
+    /// Stop events reported by the OOB task, resubscribed by every execution
+    /// command before it is sent
+    stop_events: broadcast::Receiver<StopEvent>,
+    /// Stream output of GDB and the program, filled by the OOB task
+    output: Arc<Mutex<OutputBuffer>>,
+    /// Names of the variable objects created, deleted when the session is
+    /// closed
+    var_objects: Mutex<HashSet<String>>,
+    /// Number of stops reported by the OOB task
+    stops: Arc<AtomicU64>,
+    /// Register values at the last two stops they were read, to report the
+    /// changed registers
+    registers: Mutex<RegisterSnapshot>,
+    /// Register tables with their groups, by architecture
+    register_tables: Mutex<HashMap<String, Vec<RegisterInfo>>>,
     /// OOB handle
     oob_handle: JoinHandle<()>,
 }
 
+/// Register values in hex of two stops, by register number
+#[derive(Default)]
+struct RegisterSnapshot {
+    /// Stop count when the current values were read, None if never read
+    stop: Option<u64>,
+    previous: HashMap<usize, RegisterRaw>,
+    current: HashMap<usize, RegisterRaw>,
+    /// Registers changed between the two stops, as reported by GDB
+    changed: Vec<usize>,
+}
+
 impl GDBManager {
     /// Create a new GDB session
     pub async fn create_session(
@@ -55,6 +117,7 @@ impl GDBManager {
         args: Option<Vec<OsString>>,
         tty: Option<PathBuf>,
         gdb_path: Option<PathBuf>,
+        remote: Option<RemoteTarget>,
     ) -> AppResult<String> {
         // Generate unique session ID
         let session_id = Uuid::new_v4().to_string();
@@ -67,7 +130,7 @@ impl GDBManager {
             opt_cd: cd,
             opt_bps: bps,
             opt_symbol_file: symbol_file,
-            opt_core_file: core_file,
+            opt_core_file: core_file.clone(),
             opt_proc_id: proc_id,
             opt_command: command,
             opt_source_dir: source_dir,
@@ -79,72 +142,162 @@ impl GDBManager {
         let (oob_src, mut oob_sink) = mpsc::channel(100);
         let gdb = gdb_builder.try_spawn(oob_src)?;
 
+        // Create session information
+        let info = Arc::new(Mutex::new(GDBSession {
+            id: session_id.clone(),
+            status: GDBSessionStatus::Created,
+            created_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
+            exit_code: None,
+            stop_reason: None,
+            frame: None,
+            current_thread: None,
+            attached_pid: proc_id,
+            core_file,
+            record_method: None,
+        }));
+
+        let output = Arc::new(Mutex::new(OutputBuffer::new(self.config.output_buffer_size)));
+
+        let (stop_src, stop_events) = broadcast::channel(16);
+        let oob_info = info.clone();
+        let oob_output = output.clone();
+        let stops = Arc::new(AtomicU64::new(0));
+        let oob_stops = stops.clone();
+        let oob_session_id = session_id.clone();
         let oob_handle = tokio::spawn(async move {
             loop {
                 match oob_sink.recv().await {
                     Some(record) => match record {
-                        OutOfBandRecord::AsyncRecord { results, .. } => {
+                        OutOfBandRecord::AsyncRecord { kind, class, results, .. } => {
+                            let event = if class == AsyncClass::Stopped {
+                                match serde_json::from_value::<StopEvent>(results.clone()) {
+                                    Ok(event) => Some(event),
+                                    Err(e) => {
+                                        warn!("Failed to parse stop event: {}", e);
+                                        None
+                                    }
+                                }
+                            } else {
+                                None
+                            };
+                            update_session_status(
+                                &mut *oob_info.lock().await,
+                                &class,
+                                &results,
+                                event.as_ref(),
+                            );
+                            if class == AsyncClass::Stopped {
+                                oob_stops.fetch_add(1, Ordering::SeqCst);
+                            }
+                            if let Some(event) = event {
+                                // No one waiting for the stop event is fine
+                                let _ = stop_src.send(event);
+                            }
+
+                            // Notify the client, e.g. gdb/stopped, gdb/breakpoint-modified
+                            let method = format!("gdb/{}", class);
+                            let params = json!({
+                                "session_id": oob_session_id,
+                                "kind": kind.to_string(),
+                                "class": class.to_string(),
+                                "results": results,
+                            });
                             let transport = TRANSPORT.lock().await;
                             if let Some(transport) = transport.as_ref() {
-                                if let Err(e) = transport
-                                    .send_notification("create_session", Some(results))
-                                    .await
+                                if let Err(e) =
+                                    transport.send_notification(&method, Some(params)).await
                                 {
-                                    error!("Failed to send ping to session: {:?}", e);
+                                    error!("Failed to send notification {}: {:?}", method, e);
                                 }
                             } else {
-                                warn!("Sink Channel closed");
-                                break;
+                                debug!("No transport to send notification {}", method);
                             }
                         }
-                        OutOfBandRecord::StreamRecord { data, .. } => {
+                        OutOfBandRecord::StreamRecord { kind, data } => {
                             debug!("StreamRecord: {:?}", data);
+                            oob_output.lock().await.push(kind, data);
                         }
                     },
                     None => {
                         debug!("Source Channel closed");
+                        oob_info.lock().await.status = GDBSessionStatus::Terminated;
                         break;
                     }
                 }
             }
         });
 
-        // Create session information
-        let session = GDBSession {
-            id: session_id.clone(),
-            status: GDBSessionStatus::Created,
-            created_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
-        };
-
         // Store session
-        let handle = GDBSessionHandle { info: session, gdb, oob_handle };
+        let handle = GDBSessionHandle {
+            info,
+            process: gdb.process.clone(),
+            gdb: Mutex::new(gdb),
+            stop_events,
+            output,
+            var_objects: Mutex::new(HashSet::new()),
+            stops,
+            registers: Mutex::new(RegisterSnapshot::default()),
+            register_tables: Mutex::new(HashMap::new()),
+            oob_handle,
+        };
 
-        self.sessions.lock().await.insert(session_id.clone(), handle);
+        self.sessions.lock().await.insert(session_id.clone(), Arc::new(handle));
 
         // Send empty command to GDB to flush the welcome messages
         let _ = self.send_command(&session_id, &MiCommand::empty()).await?;
 
+        if let Some(remote) = remote
+            && let Err(e) = self.connect_remote(&session_id, &remote, None).await
+        {
+            let _ = self.close_session(&session_id).await;
+            return Err(e);
+        }
+
         Ok(session_id)
     }
 
+    /// Look up a session handle, the session map is only locked during the
+    /// lookup
+    async fn get_handle(&self, session_id: &str) -> AppResult<Arc<GDBSessionHandle>> {
+        self.sessions
+            .lock()
+            .await
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(format!("Session {} does not exist", session_id)))
+    }
+
     /// Get all sessions
     pub async fn get_all_sessions(&self) -> AppResult<Vec<GDBSession>> {
-        let sessions = self.sessions.lock().await;
-        let result = sessions.values().map(|handle| handle.info.clone()).collect();
+        let handles: Vec<_> = self.sessions.lock().await.values().cloned().collect();
+        let mut result = Vec::with_capacity(handles.len());
+        for handle in handles {
+            result.push(handle.info.lock().await.clone());
+        }
         Ok(result)
     }
 
     /// Get specific session
     pub async fn get_session(&self, session_id: &str) -> AppResult<GDBSession> {
-        let sessions = self.sessions.lock().await;
-        let handle = sessions
-            .get(session_id)
-            .ok_or_else(|| AppError::NotFound(format!("Session {} does not exist", session_id)))?;
-        Ok(handle.info.clone())
+        let handle = self.get_handle(session_id).await?;
+        let info = handle.info.lock().await.clone();
+        Ok(info)
     }
 
     /// Close session
     pub async fn close_session(&self, session_id: &str) -> AppResult<()> {
+        let var_objects = match self.get_handle(session_id).await {
+            Ok(handle) => std::mem::take(&mut *handle.var_objects.lock().await),
+            Err(_) => HashSet::new(),
+        };
+        for name in var_objects {
+            let command = MiCommand::var_delete(name.as_str(), false);
+            if let Err(e) = self.send_command_with_timeout(session_id, &command).await {
+                warn!("Failed to delete variable object {}: {}", name, e);
+                break;
+            }
+        }
+
         let _ = match self.send_command_with_timeout(session_id, &MiCommand::exit()).await {
             Ok(result) => Some(result),
             Err(e) => {
@@ -154,13 +307,12 @@ impl GDBManager {
             }
         };
 
-        let mut sessions = self.sessions.lock().await;
-        let handle = sessions.remove(session_id);
+        let handle = self.sessions.lock().await.remove(session_id);
 
         if let Some(handle) = handle {
             handle.oob_handle.abort();
             // Terminate process
-            let mut process = handle.gdb.process.lock().await;
+            let mut process = handle.process.lock().await;
             let _ = process.kill().await; // Ignore possible errors, process may have already terminated
         }
 
@@ -173,13 +325,14 @@ impl GDBManager {
         session_id: &str,
         command: &MiCommand,
     ) -> AppResult<ResultRecord> {
-        let mut sessions = self.sessions.lock().await;
-        let handle = sessions
-            .get_mut(session_id)
-            .ok_or_else(|| AppError::NotFound(format!("Session {} does not exist", session_id)))?;
+        let handle = self.get_handle(session_id).await?;
 
-        let record = handle.gdb.execute(command).await?;
+        let record = handle.gdb.lock().await.execute(command).await?;
         let output = record.results.to_string();
+        if !record.console.is_empty() && command.operation != "interpreter-exec" {
+            // Not collected outside of collect_console, e.g. the welcome messages
+            let _ = CONSOLE.try_with(|console| console.borrow_mut().push_str(&record.console));
+        }
 
         debug!("GDB output: {}", output);
         Ok(record)
@@ -204,31 +357,385 @@ impl GDBManager {
         }
     }
 
-    /// Start debugging
-    pub async fn start_debugging(&self, session_id: &str) -> AppResult<String> {
-        let response = self.send_command_with_timeout(session_id, &MiCommand::exec_run()).await?;
+    /// Send an execution command and wait for the program to stop, or until
+    /// the timeout in seconds elapses
+    async fn execute_and_wait(
+        &self,
+        session_id: &str,
+        command: &MiCommand,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.send_and_wait(session_id, command, ResultClass::Running, timeout).await
+    }
+
+    /// Send a command with the expected result class and wait for the program
+    /// to stop, e.g. -target-attach which is done before the program stops
+    async fn send_and_wait(
+        &self,
+        session_id: &str,
+        command: &MiCommand,
+        expected: ResultClass,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.send_and_try_wait(session_id, command, expected, timeout)
+            .await?
+            .ok_or(AppError::GDBTimeout)
+    }
+
+    /// Same as `send_and_wait`, but returns None if the program doesn't stop
+    /// before the timeout
+    async fn send_and_try_wait(
+        &self,
+        session_id: &str,
+        command: &MiCommand,
+        expected: ResultClass,
+        timeout: Option<u64>,
+    ) -> AppResult<Option<StopEvent>> {
+        let handle = self.get_handle(session_id).await?;
+        // Subscribe before sending the command so that the stop event can't be missed
+        let mut stop_events = handle.stop_events.resubscribe();
+        let cursor = handle.output.lock().await.cursor();
+        let replaying = handle.info.lock().await.status == GDBSessionStatus::Replaying;
 
-        // Update session status
-        let mut sessions = self.sessions.lock().await;
-        if let Some(handle) = sessions.get_mut(session_id) {
-            handle.info.status = GDBSessionStatus::Running;
+        let response = check_error(self.send_command_with_timeout(session_id, command).await?)?;
+        if response.class != expected {
+            return Err(AppError::GDBError(format!(
+                "Expect the result class {:?}, got {:?}: {}",
+                expected, response.class, response.results
+            )));
         }
 
-        Ok(response.results.to_string())
+        let event = self.wait_for_stop(session_id, &mut stop_events, cursor, timeout).await?;
+        // GDB doesn't notify when the program enters or leaves the execution
+        // log, which only a reverse command or the command after it can do
+        if event.is_some()
+            && (replaying || command.is_reverse())
+            && let Err(e) = self.get_record_status(session_id).await
+        {
+            warn!("Failed to get the record status: {}", e);
+        }
+        Ok(event)
     }
 
-    /// Stop debugging
-    pub async fn stop_debugging(&self, session_id: &str) -> AppResult<String> {
-        let response =
-            self.send_command_with_timeout(session_id, &MiCommand::exec_interrupt()).await?;
+    /// Wait for the next stop event from a subscription made before resuming
+    /// the program, returns None if the timeout elapses. The event carries
+    /// the output since the cursor
+    async fn wait_for_stop(
+        &self,
+        session_id: &str,
+        stop_events: &mut broadcast::Receiver<StopEvent>,
+        cursor: u64,
+        timeout: Option<u64>,
+    ) -> AppResult<Option<StopEvent>> {
+        let handle = self.get_handle(session_id).await?;
+        let timeout = Duration::from_secs(timeout.unwrap_or(self.config.command_timeout));
+        let Ok(event) = tokio::time::timeout(timeout, async {
+            loop {
+                match stop_events.recv().await {
+                    Ok(event) => return Ok(event),
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("Missed {} stop events", n);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return Err(AppError::GDBQuit),
+                }
+            }
+        })
+        .await
+        else {
+            return Ok(None);
+        };
+        let mut event = event?;
+
+        // The OOB task stores the stream output before reporting the stop event
+        let output = handle.output.lock().await.text_since(cursor);
+        if !output.is_empty() {
+            event.output = Some(output);
+        }
+        event.decode_signal_catchpoint();
+
+        Ok(Some(event))
+    }
+
+    /// Connect to a remote target, e.g. a gdbserver or a QEMU gdbstub given by
+    /// host:port, or a serial device. A remote target reports where the program
+    /// is stopped, an extended remote target may have no program yet
+    pub async fn connect_remote(
+        &self,
+        session_id: &str,
+        target: &RemoteTarget,
+        timeout: Option<u64>,
+    ) -> AppResult<Option<StopEvent>> {
+        let handle = self.get_handle(session_id).await?;
+        // Subscribe before sending the command so that the stop event can't be missed
+        let mut stop_events = handle.stop_events.resubscribe();
+        let cursor = handle.output.lock().await.cursor();
+
+        let command = MiCommand::target_select(&target.address, target.extended);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        if response.class != ResultClass::Connected {
+            return Err(AppError::GDBError(format!(
+                "Expect the result class {:?}, got {:?}: {}",
+                ResultClass::Connected,
+                response.class,
+                response.results
+            )));
+        }
+
+        // GDB reports the threads of the remote process before ^connected and
+        // a stop once it has fetched their state. An extended remote target,
+        // e.g. a gdbserver started with --multi, may have no process yet, then
+        // there is no stop to wait for
+        if self.list_threads(session_id).await?.threads.is_empty() {
+            return Ok(None);
+        }
+        self.wait_for_stop(session_id, &mut stop_events, cursor, timeout).await
+    }
+
+    /// Copy a file from the host to the remote target
+    pub async fn put_remote_file(
+        &self,
+        session_id: &str,
+        host_file: &Path,
+        target_file: &str,
+    ) -> AppResult<()> {
+        let command = MiCommand::target_file_put(host_file, target_file);
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(())
+    }
+
+    /// Copy a file from the remote target to the host
+    pub async fn get_remote_file(
+        &self,
+        session_id: &str,
+        target_file: &str,
+        host_file: &Path,
+    ) -> AppResult<()> {
+        let command = MiCommand::target_file_get(target_file, host_file);
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(())
+    }
+
+    /// Attach to a running process and wait for it to stop
+    pub async fn attach_process(
+        &self,
+        session_id: &str,
+        pid: u32,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        let handle = self.get_handle(session_id).await?;
+        // Set before attaching so that the events of the process are tracked as attached
+        handle.info.lock().await.attached_pid = Some(pid);
 
-        // Update session status
-        let mut sessions = self.sessions.lock().await;
-        if let Some(handle) = sessions.get_mut(session_id) {
-            handle.info.status = GDBSessionStatus::Stopped;
+        let command = MiCommand::target_attach(pid);
+        let result = self.send_and_wait(session_id, &command, ResultClass::Done, timeout).await;
+        if let Err(AppError::GDBError(_)) = result {
+            handle.info.lock().await.attached_pid = None;
         }
+        result
+    }
 
-        Ok(response.results.to_string())
+    /// Detach from the process, which keeps running
+    pub async fn detach_process(&self, session_id: &str) -> AppResult<()> {
+        check_error(
+            self.send_command_with_timeout(session_id, &MiCommand::target_detach()).await?,
+        )?;
+        Ok(())
+    }
+
+    /// List the processes available to attach to, optionally only those whose
+    /// command line contains the filter
+    pub async fn list_processes(
+        &self,
+        session_id: &str,
+        filter: Option<&str>,
+    ) -> AppResult<Vec<ProcessInfo>> {
+        let command = MiCommand::list_thread_groups(true, &[]);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        let processes: Vec<ProcessInfo> = serde_json::from_value(
+            response
+                .results
+                .get("groups")
+                .ok_or(AppError::NotFound("groups not found".to_string()))?
+                .to_owned(),
+        )?;
+        Ok(match filter {
+            Some(filter) => processes
+                .into_iter()
+                .filter(|p| p.description.as_deref().is_some_and(|d| d.contains(filter)))
+                .collect(),
+            None => processes,
+        })
+    }
+
+    /// Load a core dump, or reload the one of the session, and report the
+    /// signal, the faulting address and its mapping, the registers and the
+    /// instructions of the crashed thread, and the backtraces of all threads
+    /// with at most `max_frames` frames each
+    pub async fn analyze_core(
+        &self,
+        session_id: &str,
+        core_file: Option<PathBuf>,
+        max_frames: usize,
+    ) -> AppResult<CoreReport> {
+        let handle = self.get_handle(session_id).await?;
+        let core_file = match core_file {
+            Some(core_file) => core_file,
+            None => handle.info.lock().await.core_file.clone().ok_or(AppError::InvalidArgument(
+                "no core file loaded in the session".to_string(),
+            ))?,
+        };
+        // The terminating signal is only printed to the console when the core
+        // is loaded, so it is loaded again even if it is the same
+        let command = MiCommand::target_select_core(&core_file);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        handle.info.lock().await.core_file = Some(core_file.clone());
+
+        let (signal_name, signal_meaning) = parse_terminating_signal(&response.console).unzip();
+        let signal_number = self.evaluate_u64(session_id, "$_siginfo.si_signo").await.ok();
+        let fault_address = if signal_name
+            .as_deref()
+            .is_none_or(|name| matches!(name, "SIGSEGV" | "SIGBUS" | "SIGILL" | "SIGFPE"))
+        {
+            self.evaluate_u64(session_id, "$_siginfo._sifields._sigfault.si_addr").await.ok()
+        } else {
+            None
+        };
+
+        let mappings = self.get_memory_mappings(session_id).await;
+        let find_mapping = |address: u64| mappings.iter().find(|m| m.contains(address)).cloned();
+        let pc = self.get_pc(session_id).await.ok();
+
+        let registers = self
+            .get_registers(session_id, None, RegisterFormat::Hex)
+            .await?
+            .into_iter()
+            .filter(|r| matches!(r.value, Some(RegisterRaw::U32(_) | RegisterRaw::U64(_))))
+            .collect();
+        // The pc may point to unreadable memory, e.g. after a call through a
+        // null function pointer
+        let disassembly =
+            self.disassemble(session_id, DisassembleLocation::AroundPc(8), false).await.ok();
+
+        let thread_list = self.list_threads(session_id).await?;
+        let mut threads = vec![];
+        for thread in thread_list.threads {
+            let id = thread.id.parse()?;
+            let mut stack = self
+                .get_stack_frames(session_id, Some(id), None, Some(max_frames.saturating_sub(1)))
+                .await?;
+            for frame in stack.frames.iter_mut() {
+                frame.locals = self
+                    .get_local_variables(session_id, Some(id), Some(frame.level as usize))
+                    .await
+                    .ok();
+            }
+            threads.push(ThreadBacktrace {
+                id: thread.id,
+                target_id: thread.target_id,
+                name: thread.name,
+                depth: stack.depth,
+                frames: stack.frames,
+            });
+        }
+
+        Ok(CoreReport {
+            core_file,
+            signal_name,
+            signal_meaning,
+            signal_number: signal_number.map(|n| n as i32),
+            fault_address: fault_address.map(Address),
+            fault_mapping: fault_address.and_then(find_mapping),
+            crashed_thread_id: thread_list.current_thread_id,
+            pc_mapping: pc.and_then(find_mapping),
+            registers,
+            threads,
+            disassembly,
+        })
+    }
+
+    /// Read the stream output of a session from the cursor, reads from the
+    /// oldest output kept if no cursor is given
+    pub async fn read_output(
+        &self,
+        session_id: &str,
+        cursor: Option<u64>,
+        limit: Option<usize>,
+    ) -> AppResult<OutputPage> {
+        let handle = self.get_handle(session_id).await?;
+        let output = handle.output.lock().await;
+        Ok(output.read(cursor.unwrap_or(0), limit.unwrap_or(100)))
+    }
+
+    /// Execute a CLI command, returns the console output of the command
+    pub async fn execute_cli(&self, session_id: &str, command: &str) -> AppResult<String> {
+        let response = check_error(
+            self.send_command_with_timeout(session_id, &MiCommand::cli_exec(command)).await?,
+        )?;
+
+        Ok(response.console)
+    }
+
+    /// Execute an arbitrary MI command, errors reported by GDB are returned as
+    /// the result instead of failing
+    pub async fn execute_mi(
+        &self,
+        session_id: &str,
+        operation: &str,
+        options: Option<Vec<String>>,
+        parameters: Option<Vec<String>>,
+    ) -> AppResult<MiResult> {
+        let command = MiCommand::raw(operation, options, parameters);
+        if command.operation.is_empty() {
+            return Err(AppError::InvalidArgument("operation must not be empty".to_string()));
+        }
+        let response = self.send_command_with_timeout(session_id, &command).await?;
+
+        Ok(MiResult {
+            class: response.class,
+            results: response.results,
+            console: Some(response.console).filter(|console| !console.is_empty()),
+        })
+    }
+
+    /// Start debugging
+    pub async fn start_debugging(
+        &self,
+        session_id: &str,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.execute_and_wait(session_id, &MiCommand::exec_run(), timeout).await
+    }
+
+    /// Interrupt the program and wait for it to stop
+    pub async fn stop_debugging(
+        &self,
+        session_id: &str,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        let command = MiCommand::exec_interrupt();
+        match self.send_and_try_wait(session_id, &command, ResultClass::Done, timeout).await {
+            Ok(Some(event)) => return Ok(event),
+            // GDB doesn't read commands while the program runs in sync mode,
+            // the late result of -exec-interrupt is dropped by the next command
+            Ok(None) | Err(AppError::GDBTimeout) => {}
+            Err(e) => return Err(e),
+        }
+
+        // Some targets ignore -exec-interrupt, interrupt GDB like Ctrl-C instead
+        warn!("The program didn't stop after -exec-interrupt, sending SIGINT to GDB");
+        let handle = self.get_handle(session_id).await?;
+        let mut stop_events = handle.stop_events.resubscribe();
+        let cursor = handle.output.lock().await.cursor();
+        handle
+            .gdb
+            .lock()
+            .await
+            .interrupt_execution()
+            .await
+            .map_err(|e| AppError::GDBError(format!("Failed to interrupt GDB: {}", e)))?;
+        self.wait_for_stop(session_id, &mut stop_events, cursor, timeout)
+            .await?
+            .ok_or(AppError::GDBTimeout)
     }
 
     /// Get breakpoint list
@@ -248,11 +755,11 @@ impl GDBManager {
     pub async fn set_breakpoint(
         &self,
         session_id: &str,
-        file: &Path,
-        line: usize,
+        location: BreakPointLocation<'_>,
+        options: &BreakPointOptions,
     ) -> AppResult<BreakPoint> {
-        let command = MiCommand::insert_breakpoint(BreakPointLocation::Line(file, line));
-        let response = self.send_command_with_timeout(session_id, &command).await?;
+        let command = MiCommand::insert_breakpoint(location, options);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
 
         Ok(serde_json::from_value(
             response
@@ -263,18 +770,56 @@ impl GDBManager {
         )?)
     }
 
+    /// Set watchpoint
+    pub async fn set_watchpoint(
+        &self,
+        session_id: &str,
+        expression: &str,
+        mode: WatchMode,
+    ) -> AppResult<WatchPoint> {
+        let command = MiCommand::insert_watchpoint(expression, mode);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+
+        // The result is named after the watchpoint kind
+        let watchpoint = ["wpt", "hw-rwpt", "hw-awpt"]
+            .iter()
+            .find_map(|key| response.results.get(key))
+            .ok_or(AppError::NotFound("wpt not found in the result".to_string()))?;
+        Ok(serde_json::from_value(watchpoint.to_owned())?)
+    }
+
+    /// Set a catchpoint, see `MiCommand::catch` for the argument
+    pub async fn set_catchpoint(
+        &self,
+        session_id: &str,
+        event: CatchEvent,
+        argument: Option<&str>,
+        temporary: bool,
+    ) -> AppResult<BreakPoint> {
+        let command = MiCommand::catch(event, argument, temporary);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        if let Some(bkpt) = response.results.get("bkpt") {
+            return Ok(serde_json::from_value(bkpt.to_owned())?);
+        }
+
+        // The CLI catch commands only print the number of the catchpoint
+        let (number, _) = parse_catchpoint(&response.console)
+            .ok_or(AppError::NotFound("catchpoint number not found".to_string()))?;
+        let number: BreakPointNumber = number.parse()?;
+        self.get_breakpoints(session_id)
+            .await?
+            .into_iter()
+            .find(|bp| bp.number == number)
+            .ok_or(AppError::NotFound(format!("catchpoint {} not found", number)))
+    }
+
     /// Delete breakpoint
     pub async fn delete_breakpoint(
         &self,
         session_id: &str,
         breakpoints: Vec<String>,
     ) -> AppResult<()> {
-        let command = MiCommand::delete_breakpoints(
-            breakpoints
-                .iter()
-                .map(|num| serde_json::from_str::<BreakPointNumber>(num))
-                .collect::<Result<Vec<_>, _>>()?,
-        );
+        let command = MiCommand::delete_breakpoints(parse_breakpoint_numbers(&breakpoints)?);
         let response = self.send_command_with_timeout(session_id, &command).await?;
         if response.class != ResultClass::Done {
             return Err(AppError::GDBError(response.results.to_string()));
@@ -283,17 +828,182 @@ impl GDBManager {
         Ok(())
     }
 
-    /// Get stack frames
-    pub async fn get_stack_frames(&self, session_id: &str) -> AppResult<Vec<StackFrame>> {
-        let command = MiCommand::stack_list_frames(None, None);
-        let response = self.send_command_with_timeout(session_id, &command).await?;
+    /// Enable breakpoints
+    pub async fn enable_breakpoints(
+        &self,
+        session_id: &str,
+        breakpoints: Vec<String>,
+    ) -> AppResult<()> {
+        let command = MiCommand::enable_breakpoints(&parse_breakpoint_numbers(&breakpoints)?);
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(())
+    }
 
-        Ok(serde_json::from_value(
+    /// Disable breakpoints
+    pub async fn disable_breakpoints(
+        &self,
+        session_id: &str,
+        breakpoints: Vec<String>,
+    ) -> AppResult<()> {
+        let command = MiCommand::disable_breakpoints(&parse_breakpoint_numbers(&breakpoints)?);
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(())
+    }
+
+    /// Set or remove the condition of a breakpoint
+    pub async fn set_breakpoint_condition(
+        &self,
+        session_id: &str,
+        breakpoint: &str,
+        condition: Option<&str>,
+    ) -> AppResult<()> {
+        let command = MiCommand::breakpoint_condition(breakpoint.trim().parse()?, condition);
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(())
+    }
+
+    /// Set the ignore count of a breakpoint
+    pub async fn set_breakpoint_ignore_count(
+        &self,
+        session_id: &str,
+        breakpoint: &str,
+        count: usize,
+    ) -> AppResult<()> {
+        let command = MiCommand::breakpoint_after(breakpoint.trim().parse()?, count);
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(())
+    }
+
+    /// Set the commands executed when a breakpoint is hit
+    pub async fn set_breakpoint_commands(
+        &self,
+        session_id: &str,
+        breakpoint: &str,
+        commands: Vec<String>,
+    ) -> AppResult<()> {
+        let command = MiCommand::breakpoint_commands(breakpoint.trim().parse()?, &commands);
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(())
+    }
+
+    /// Get the stack frames between low_frame and high_frame, inclusive, with
+    /// their arguments and the depth of the stack
+    pub async fn get_stack_frames(
+        &self,
+        session_id: &str,
+        thread: Option<usize>,
+        low_frame: Option<usize>,
+        high_frame: Option<usize>,
+    ) -> AppResult<StackFrames> {
+        let command = MiCommand::stack_info_depth().thread_frame(thread, None);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        let depth = get_string(&response.results, "depth")?.parse()?;
+
+        let command =
+            MiCommand::stack_list_frames(low_frame, high_frame).thread_frame(thread, None);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        let mut frames: Vec<StackFrame> = serde_json::from_value(
             response
                 .results
                 .get("stack")
                 .ok_or(AppError::NotFound("stack not found".to_string()))?
                 .to_owned(),
+        )?;
+
+        let arguments = self.get_frame_arguments(session_id, thread, low_frame, high_frame).await?;
+        for frame in frames.iter_mut() {
+            frame.args = arguments.iter().find(|a| a.level == frame.level).map(|a| a.args.clone());
+        }
+        Ok(StackFrames { depth, frames })
+    }
+
+    /// Get the information of a frame, or of the selected frame
+    pub async fn get_frame_info(
+        &self,
+        session_id: &str,
+        thread: Option<usize>,
+        frame: Option<usize>,
+    ) -> AppResult<StackFrame> {
+        let (thread, command) = match frame {
+            Some(frame) => {
+                // GDB only takes a frame along with its thread
+                let thread = match thread {
+                    Some(thread) => thread,
+                    None => self
+                        .list_threads(session_id)
+                        .await?
+                        .current_thread_id
+                        .ok_or(AppError::NotFound("no thread is selected".to_string()))?
+                        .parse()?,
+                };
+                (Some(thread), MiCommand::stack_info_frame(Some((thread, frame))))
+            }
+            None => (thread, MiCommand::stack_info_frame(None).thread_frame(thread, None)),
+        };
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        let mut frame: StackFrame = serde_json::from_value(
+            response
+                .results
+                .get("frame")
+                .ok_or(AppError::NotFound("frame not found".to_string()))?
+                .to_owned(),
+        )?;
+
+        let level = Some(frame.level as usize);
+        let arguments = self.get_frame_arguments(session_id, thread, level, level).await?;
+        frame.args = arguments.into_iter().next().map(|a| a.args);
+        Ok(frame)
+    }
+
+    /// Select the frame used by the following commands and return its
+    /// information
+    pub async fn select_frame(&self, session_id: &str, frame: usize) -> AppResult<StackFrame> {
+        let command = MiCommand::select_frame(frame as u64);
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        self.get_frame_info(session_id, None, None).await
+    }
+
+    async fn get_frame_arguments(
+        &self,
+        session_id: &str,
+        thread: Option<usize>,
+        low_frame: Option<usize>,
+        high_frame: Option<usize>,
+    ) -> AppResult<Vec<FrameArguments>> {
+        let command =
+            MiCommand::stack_list_arguments(PrintValue::SimpleValues, low_frame, high_frame)
+                .thread_frame(thread, None);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(serde_json::from_value(
+            response
+                .results
+                .get("stack-args")
+                .ok_or(AppError::NotFound("stack-args not found".to_string()))?
+                .to_owned(),
+        )?)
+    }
+
+    /// List the threads of the program
+    pub async fn list_threads(&self, session_id: &str) -> AppResult<ThreadList> {
+        let command = MiCommand::thread_info(None);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(serde_json::from_value(response.results)?)
+    }
+
+    /// Select a thread and return its current frame
+    pub async fn select_thread(&self, session_id: &str, thread: usize) -> AppResult<StackFrame> {
+        let command = MiCommand::thread_select(thread);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+
+        // =thread-selected is not emitted for -thread-select
+        let handle = self.get_handle(session_id).await?;
+        handle.info.lock().await.current_thread = Some(thread.to_string());
+        Ok(serde_json::from_value(
+            response
+                .results
+                .get("frame")
+                .ok_or(AppError::NotFound("frame not found".to_string()))?
+                .to_owned(),
         )?)
     }
 
@@ -301,9 +1011,10 @@ impl GDBManager {
     pub async fn get_local_variables(
         &self,
         session_id: &str,
+        thread: Option<usize>,
         frame_id: Option<usize>,
     ) -> AppResult<Vec<Variable>> {
-        let command = MiCommand::stack_list_variables(None, frame_id, None);
+        let command = MiCommand::stack_list_variables(thread, frame_id, None);
         let response = self.send_command_with_timeout(session_id, &command).await?;
 
         Ok(serde_json::from_value(
@@ -315,35 +1026,15 @@ impl GDBManager {
         )?)
     }
 
-    /// Get registers
+    /// Get registers, by name or number, all of them by default
     pub async fn get_registers(
         &self,
         session_id: &str,
         reg_list: Option<Vec<String>>,
+        format: RegisterFormat,
     ) -> AppResult<Vec<Register>> {
-        let reg_list = reg_list
-            .map(|s| s.iter().map(|num| num.parse::<usize>()).collect::<Result<Vec<_>, _>>())
-            .transpose()?;
-        let command = MiCommand::data_list_register_names(reg_list.clone());
-        let response = self.send_command_with_timeout(session_id, &command).await?;
-        let names: Vec<String> = serde_json::from_value(
-            response
-                .results
-                .get("register-names")
-                .ok_or(AppError::NotFound("register-names not found".to_string()))?
-                .to_owned(),
-        )?;
-
-        let command = MiCommand::data_list_register_values(RegisterFormat::Hex, reg_list);
-        let response = self.send_command_with_timeout(session_id, &command).await?;
-
-        let registers: Vec<Register> = serde_json::from_value(
-            response
-                .results
-                .get("register-values")
-                .ok_or(AppError::NotFound("expect register-values".to_string()))?
-                .to_owned(),
-        )?;
+        let (names, reg_list) = self.resolve_registers(session_id, reg_list).await?;
+        let registers = self.list_register_values(session_id, format, reg_list).await?;
         Ok(registers
             .into_iter()
             .map(|mut r| {
@@ -353,18 +1044,121 @@ impl GDBManager {
             .collect::<_>())
     }
 
-    /// Get register names
-    pub async fn get_register_names(
+    /// Get the registers changed since the previous stop the registers were
+    /// read, with their previous values. On the first read all registers are
+    /// reported as changed
+    pub async fn get_changed_registers(
         &self,
         session_id: &str,
         reg_list: Option<Vec<String>>,
-    ) -> AppResult<Vec<Register>> {
-        let reg_list = reg_list
-            .map(|s| s.iter().map(|num| num.parse::<usize>()).collect::<Result<Vec<_>, _>>())
+        format: RegisterFormat,
+    ) -> AppResult<Vec<RegisterChange>> {
+        let handle = self.get_handle(session_id).await?;
+        let (names, reg_list) = self.resolve_registers(session_id, reg_list).await?;
+
+        let stop = handle.stops.load(Ordering::SeqCst);
+        let mut snapshot = handle.registers.lock().await;
+        if snapshot.stop != Some(stop) {
+            // GDB reports the changes since the last time it was asked, which
+            // is when the snapshot was last taken
+            let command = MiCommand::data_list_changed_registers();
+            let response =
+                check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+            let changed: Vec<String> = serde_json::from_value(
+                response
+                    .results
+                    .get("changed-registers")
+                    .ok_or(AppError::NotFound("expect changed-registers".to_string()))?
+                    .to_owned(),
+            )?;
+            let values = self.list_register_values(session_id, RegisterFormat::Hex, None).await?;
+
+            snapshot.previous = std::mem::take(&mut snapshot.current);
+            snapshot.current =
+                values.into_iter().filter_map(|r| r.value.map(|v| (r.number, v))).collect();
+            snapshot.changed = changed.iter().filter_map(|n| n.parse().ok()).collect();
+            snapshot.stop = Some(stop);
+        }
+
+        let changed: Vec<usize> = snapshot
+            .changed
+            .iter()
+            .copied()
+            .filter(|n| reg_list.as_ref().is_none_or(|list| list.contains(n)))
+            .collect();
+        if changed.is_empty() {
+            return Ok(vec![]);
+        }
+        let values = if format == RegisterFormat::Hex {
+            snapshot.current.clone()
+        } else {
+            self.list_register_values(session_id, format, Some(changed.clone()))
+                .await?
+                .into_iter()
+                .filter_map(|r| r.value.map(|v| (r.number, v)))
+                .collect()
+        };
+
+        Ok(changed
+            .into_iter()
+            .map(|number| RegisterChange {
+                name: names.get(number).cloned(),
+                number,
+                previous: snapshot.previous.get(&number).cloned(),
+                value: values.get(&number).cloned(),
+            })
+            .collect())
+    }
+
+    /// Get the names of all registers, indexed by number, and resolve the
+    /// registers given by name or number to their numbers
+    async fn resolve_registers(
+        &self,
+        session_id: &str,
+        reg_list: Option<Vec<String>>,
+    ) -> AppResult<(Vec<String>, Option<Vec<usize>>)> {
+        let command = MiCommand::data_list_register_names(None);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        let names: Vec<String> = serde_json::from_value(
+            response
+                .results
+                .get("register-names")
+                .ok_or(AppError::NotFound("register-names not found".to_string()))?
+                .to_owned(),
+        )?;
+
+        let numbers = reg_list
+            .map(|list| {
+                list.iter()
+                    .map(|register| {
+                        let register = register.trim_start_matches('$');
+                        register
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|number| *number < names.len())
+                            .or_else(|| {
+                                names.iter().position(|name| !name.is_empty() && name == register)
+                            })
+                            .ok_or(AppError::InvalidArgument(format!(
+                                "unknown register {}",
+                                register
+                            )))
+                    })
+                    .collect::<AppResult<Vec<_>>>()
+            })
             .transpose()?;
-        let command = MiCommand::data_list_register_names(reg_list);
-        let response = self.send_command_with_timeout(session_id, &command).await?;
+        Ok((names, numbers))
+    }
 
+    /// Get the values of registers, by number
+    async fn list_register_values(
+        &self,
+        session_id: &str,
+        format: RegisterFormat,
+        reg_list: Option<Vec<usize>>,
+    ) -> AppResult<Vec<Register>> {
+        let command = MiCommand::data_list_register_values(format, reg_list);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
         Ok(serde_json::from_value(
             response
                 .results
@@ -374,6 +1168,70 @@ impl GDBManager {
         )?)
     }
 
+    /// Get the registers of the current architecture with their numbers and
+    /// groups, optionally only those of a group. The table is cached per
+    /// architecture. If GDB can't print the register groups, they are left
+    /// empty and the registers of a group are listed by `info registers`
+    pub async fn get_register_names(
+        &self,
+        session_id: &str,
+        group: Option<&str>,
+    ) -> AppResult<Vec<RegisterInfo>> {
+        let handle = self.get_handle(session_id).await?;
+        let console = self.execute_cli(session_id, "show architecture").await?;
+        let arch = parse_architecture(&console).unwrap_or_default();
+
+        let mut tables = handle.register_tables.lock().await;
+        if !tables.contains_key(&arch) {
+            let (names, _) = self.resolve_registers(session_id, None).await?;
+            // Not all targets support the maintenance command, the groups are
+            // then unknown
+            let mut groups = self
+                .execute_cli(session_id, "maint print register-groups")
+                .await
+                .map(|output| parse_register_groups(&output))
+                .unwrap_or_default();
+            let table = names
+                .into_iter()
+                .enumerate()
+                .filter(|(_, name)| !name.is_empty())
+                .map(|(number, name)| RegisterInfo {
+                    number,
+                    name,
+                    groups: groups.remove(&number).unwrap_or_default(),
+                })
+                .collect();
+            tables.insert(arch.clone(), table);
+        }
+        let table = &tables[&arch];
+
+        let Some(group) = group else {
+            return Ok(table.clone());
+        };
+        // Without the maintenance command, ask GDB for the registers of the group
+        if table.iter().all(|r| r.groups.is_empty()) {
+            let console =
+                self.execute_cli(session_id, &format!("info registers {}", group)).await?;
+            let names = parse_info_registers(&console);
+            return Ok(table.iter().filter(|r| names.contains(&r.name)).cloned().collect());
+        }
+
+        let registers: Vec<RegisterInfo> =
+            table.iter().filter(|r| r.groups.iter().any(|g| g == group)).cloned().collect();
+        if registers.is_empty() {
+            let mut known: Vec<&str> =
+                table.iter().flat_map(|r| r.groups.iter().map(|g| g.as_str())).collect();
+            known.sort_unstable();
+            known.dedup();
+            return Err(AppError::InvalidArgument(format!(
+                "unknown register group {}, known groups: {}",
+                group,
+                known.join(", ")
+            )));
+        }
+        Ok(registers)
+    }
+
     /// Read memory contents
     pub async fn read_memory(
         &self,
@@ -394,36 +1252,689 @@ impl GDBManager {
         )?)
     }
 
+    /// Write the hex encoded contents at the address, repeated `repeat` times,
+    /// and return the number of bytes written. If the mappings of the process
+    /// are known, the bytes must all be in the same mapping
+    pub async fn write_memory(
+        &self,
+        session_id: &str,
+        address: &str,
+        contents: &str,
+        repeat: Option<usize>,
+    ) -> AppResult<u64> {
+        if contents.is_empty()
+            || !contents.len().is_multiple_of(2)
+            || !contents.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(AppError::InvalidArgument(format!(
+                "contents must be hex encoded bytes, got {:?}",
+                contents
+            )));
+        }
+        if repeat == Some(0) {
+            return Err(AppError::InvalidArgument("repeat must be at least 1".to_string()));
+        }
+        let length = (contents.len() / 2)
+            .checked_mul(repeat.unwrap_or(1))
+            .map(|length| length as u64)
+            .ok_or(AppError::InvalidArgument("contents repeated too many times".to_string()))?;
+        let start = self.evaluate_u64(session_id, address).await?;
+        let end = start.checked_add(length).ok_or(AppError::InvalidArgument(format!(
+            "writing {} bytes at 0x{:x} overflows the address space",
+            length, start
+        )))?;
+
+        let mappings = self.get_memory_mappings(session_id).await;
+        if !mappings.is_empty() {
+            let mapping = mappings
+                .iter()
+                .find(|m| m.contains(start))
+                .ok_or(AppError::InvalidArgument(format!("address 0x{:x} is not mapped", start)))?;
+            if end > mapping.end_address {
+                return Err(AppError::InvalidArgument(format!(
+                    "writing {} bytes at 0x{:x} overflows the mapping 0x{:x}-0x{:x}",
+                    length, start, mapping.start_address, mapping.end_address
+                )));
+            }
+        }
+
+        let count = repeat.map(|_| length as usize);
+        let command =
+            MiCommand::data_write_memory_bytes(&format!("0x{:x}", start), contents, count);
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(length)
+    }
+
+    /// Set a register, by name or number, to the value of an expression and
+    /// return its new value
+    pub async fn set_register(
+        &self,
+        session_id: &str,
+        register: &str,
+        value: &str,
+    ) -> AppResult<Register> {
+        let (_, numbers) =
+            self.resolve_registers(session_id, Some(vec![register.to_owned()])).await?;
+        let number = numbers
+            .into_iter()
+            .flatten()
+            .next()
+            .ok_or(AppError::NotFound(format!("register {} not found", register)))?;
+        let command =
+            MiCommand::data_write_register_values(RegisterFormat::Hex, &[(number, value)]);
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        // The snapshot is stale, the next read reports the write as a change
+        self.get_handle(session_id).await?.registers.lock().await.stop = None;
+        self.get_registers(session_id, Some(vec![number.to_string()]), RegisterFormat::Hex)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(AppError::NotFound(format!("register {} not found", register)))
+    }
+
+    /// Get the memory mappings of the process, empty if they are not available,
+    /// e.g. for remote targets or cores without file notes
+    async fn get_memory_mappings(&self, session_id: &str) -> Vec<MemoryMapping> {
+        self.execute_cli(session_id, "info proc mappings")
+            .await
+            .map(|output| parse_memory_mappings(&output))
+            .unwrap_or_default()
+    }
+
+    /// Disassemble a function, source lines, an address range or the
+    /// instructions around the program counter
+    pub async fn disassemble(
+        &self,
+        session_id: &str,
+        location: DisassembleLocation<'_>,
+        source: bool,
+    ) -> AppResult<Disassembly> {
+        let mode = if source {
+            DisassembleMode::MixedSourceAndDisassemblyWithRawOpcodes
+        } else {
+            DisassembleMode::DisassemblyWithRawOpcodes
+        };
+        // Not available if the program is not running
+        let pc = self.get_pc(session_id).await.ok();
+        let command = match location {
+            DisassembleLocation::Function(function) => {
+                MiCommand::data_disassemble_function(function, mode)
+            }
+            DisassembleLocation::File(file, line, lines) => {
+                MiCommand::data_disassemble_file(file, line, lines, mode)
+            }
+            DisassembleLocation::Range(start, end) => {
+                MiCommand::data_disassemble_address(start, end, mode)
+            }
+            DisassembleLocation::AroundPc(count) => {
+                let pc = pc.ok_or(AppError::InvalidArgument(
+                    "the program is not running, there is no $pc".to_string(),
+                ))?;
+                let instructions = self.disassemble_around(session_id, pc, count, mode).await?;
+                return Ok(Disassembly { pc: Some(Address(pc)), instructions });
+            }
+        };
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        let instructions = parse_asm_insns(
+            response
+                .results
+                .get("asm_insns")
+                .ok_or(AppError::NotFound("expect asm_insns in result".to_string()))?
+                .to_owned(),
+        )?;
+        Ok(Disassembly { pc: pc.map(Address), instructions })
+    }
+
+    /// Disassemble `count` instructions before and after the program counter
+    async fn disassemble_around(
+        &self,
+        session_id: &str,
+        pc: u64,
+        count: usize,
+        mode: DisassembleMode,
+    ) -> AppResult<Vec<AsmInstruction>> {
+        // The start of an instruction before pc can't be known on architectures
+        // with variable length instructions, disassemble the whole function
+        // instead and fall back to the instructions after pc without symbols
+        let command = MiCommand::data_disassemble_function("$pc", mode);
+        let response = match self.send_command_with_timeout(session_id, &command).await? {
+            response if response.class == ResultClass::Error => {
+                let end = format!("$pc + {}", count.max(1) * 16);
+                let command = MiCommand::data_disassemble_address("$pc", &end, mode);
+                check_error(self.send_command_with_timeout(session_id, &command).await?)?
+            }
+            response => response,
+        };
+        let instructions = parse_asm_insns(
+            response
+                .results
+                .get("asm_insns")
+                .ok_or(AppError::NotFound("expect asm_insns in result".to_string()))?
+                .to_owned(),
+        )?;
+
+        let index = instructions.iter().position(|i| i.address.0 == pc).unwrap_or(0);
+        let start = index.saturating_sub(count);
+        let end = (index + count + 1).min(instructions.len());
+        Ok(instructions[start..end].to_vec())
+    }
+
+    /// Get the program counter of the selected frame
+    async fn get_pc(&self, session_id: &str) -> AppResult<u64> {
+        self.evaluate_u64(session_id, "$pc").await
+    }
+
+    /// Evaluate an expression in the selected frame as an unsigned integer
+    async fn evaluate_u64(&self, session_id: &str, expression: &str) -> AppResult<u64> {
+        let expression = format!("(unsigned long long) ({})", expression);
+        let command = MiCommand::data_evaluate_expression(&expression);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(get_string(&response.results, "value")?.parse()?)
+    }
+
     /// Continue execution
-    pub async fn continue_execution(&self, session_id: &str) -> AppResult<String> {
-        let response =
-            self.send_command_with_timeout(session_id, &MiCommand::exec_continue()).await?;
+    pub async fn continue_execution(
+        &self,
+        session_id: &str,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.execute_and_wait(session_id, &MiCommand::exec_continue(), timeout).await
+    }
+
+    /// Step execution, `count` times
+    pub async fn step_execution(
+        &self,
+        session_id: &str,
+        count: Option<usize>,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.execute_and_wait(session_id, &MiCommand::exec_step().repeat(count), timeout).await
+    }
+
+    /// Next execution, `count` times
+    pub async fn next_execution(
+        &self,
+        session_id: &str,
+        count: Option<usize>,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.execute_and_wait(session_id, &MiCommand::exec_next().repeat(count), timeout).await
+    }
+
+    /// Step into the next instruction, `count` times
+    pub async fn step_instruction(
+        &self,
+        session_id: &str,
+        count: Option<usize>,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        let command = MiCommand::exec_step_instruction().repeat(count);
+        self.execute_and_wait(session_id, &command, timeout).await
+    }
+
+    /// Step over the next instruction, `count` times
+    pub async fn next_instruction(
+        &self,
+        session_id: &str,
+        count: Option<usize>,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        let command = MiCommand::exec_next_instruction().repeat(count);
+        self.execute_and_wait(session_id, &command, timeout).await
+    }
+
+    /// Run until the current function returns, the stop event has the
+    /// returned value
+    pub async fn finish_execution(
+        &self,
+        session_id: &str,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.execute_and_wait(session_id, &MiCommand::exec_finish(), timeout).await
+    }
+
+    /// Run until a line after the current one, e.g. to leave a loop, or until
+    /// the location, stopping anyway when the current function returns
+    pub async fn until_execution(
+        &self,
+        session_id: &str,
+        location: Option<&str>,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.execute_and_wait(session_id, &MiCommand::exec_until(location), timeout).await
+    }
+
+    /// Run until the location, stopping anyway when the current function
+    /// returns
+    pub async fn advance_execution(
+        &self,
+        session_id: &str,
+        location: &str,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.execute_and_wait(session_id, &MiCommand::advance(location), timeout).await
+    }
+
+    /// Resume the program at the location
+    pub async fn jump_execution(
+        &self,
+        session_id: &str,
+        location: &str,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.execute_and_wait(session_id, &MiCommand::exec_jump(location), timeout).await
+    }
+
+    /// Make the current function return immediately, optionally with a value,
+    /// and return the frame of the caller. The program doesn't run
+    pub async fn return_from_function(
+        &self,
+        session_id: &str,
+        value: Option<&str>,
+    ) -> AppResult<StackFrame> {
+        let command = MiCommand::exec_return(value);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        let frame: StackFrame = serde_json::from_value(
+            response
+                .results
+                .get("frame")
+                .ok_or(AppError::NotFound("frame not found".to_string()))?
+                .to_owned(),
+        )?;
+
+        // There is no stop event
+        let handle = self.get_handle(session_id).await?;
+        handle.info.lock().await.frame = Some(frame.clone());
+        Ok(frame)
+    }
 
-        // Update session status
-        let mut sessions = self.sessions.lock().await;
-        if let Some(handle) = sessions.get_mut(session_id) {
-            handle.info.status = GDBSessionStatus::Running;
+    /// Start recording the program so that it can be executed backwards
+    pub async fn start_recording(&self, session_id: &str, method: RecordMethod) -> AppResult<()> {
+        let command = MiCommand::record_start(method);
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(())
+    }
+
+    /// Stop recording the program, the execution log is deleted
+    pub async fn stop_recording(&self, session_id: &str) -> AppResult<()> {
+        let command = MiCommand::record_stop();
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(())
+    }
+
+    /// Get the status of the process record and track in the session whether
+    /// the program is replaying the execution log
+    pub async fn get_record_status(&self, session_id: &str) -> AppResult<RecordStatus> {
+        let command = MiCommand::record_info();
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        let status = parse_record_status(&response.console);
+
+        let handle = self.get_handle(session_id).await?;
+        let mut info = handle.info.lock().await;
+        match info.status {
+            GDBSessionStatus::Stopped if status.replaying => {
+                info.status = GDBSessionStatus::Replaying;
+            }
+            GDBSessionStatus::Replaying if !status.replaying => {
+                info.status = GDBSessionStatus::Stopped;
+            }
+            _ => {}
         }
+        Ok(status)
+    }
 
-        Ok(response.results.to_string())
+    /// Step backwards into the previous line
+    pub async fn reverse_step(
+        &self,
+        session_id: &str,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.execute_and_wait(session_id, &MiCommand::exec_step().reverse(), timeout).await
     }
 
-    /// Step execution
-    pub async fn step_execution(&self, session_id: &str) -> AppResult<String> {
-        let response = self.send_command_with_timeout(session_id, &MiCommand::exec_step()).await?;
+    /// Step backwards over the previous line
+    pub async fn reverse_next(
+        &self,
+        session_id: &str,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.execute_and_wait(session_id, &MiCommand::exec_next().reverse(), timeout).await
+    }
 
-        Ok(response.results.to_string())
+    /// Continue backwards until a breakpoint or the start of the execution log
+    pub async fn reverse_continue(
+        &self,
+        session_id: &str,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.execute_and_wait(session_id, &MiCommand::exec_continue().reverse(), timeout).await
     }
 
-    /// Next execution
-    pub async fn next_execution(&self, session_id: &str) -> AppResult<String> {
-        let response = self.send_command_with_timeout(session_id, &MiCommand::exec_next()).await?;
+    /// Go backwards to the call of the current function
+    pub async fn reverse_finish(
+        &self,
+        session_id: &str,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.execute_and_wait(session_id, &MiCommand::exec_finish().reverse(), timeout).await
+    }
 
-    /// Modify variable value
-    pub async fn modify_variable(&self, session_id: &str, expression: String) -> AppResult<String> {
-        let command = MiCommand::data_evaluate_expression(expression);
-        let response = self.send_command_with_timeout(session_id, &command).await?;
+    /// Configure how GDB handles a signal, returns the handling of the signal
+    /// which is only listed if no action is given
+    pub async fn handle_signal(
+        &self,
+        session_id: &str,
+        signal: &str,
+        actions: &[&str],
+    ) -> AppResult<Vec<SignalHandling>> {
+        let command = MiCommand::handle_signal(signal, actions);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(parse_signal_handling(&response.console))
+    }
+
+    /// Resume the program with a signal, "0" resumes it without any signal,
+    /// and wait for it to stop
+    pub async fn send_signal(
+        &self,
+        session_id: &str,
+        signal: &str,
+        timeout: Option<u64>,
+    ) -> AppResult<StopEvent> {
+        self.execute_and_wait(session_id, &MiCommand::signal(signal), timeout).await
+    }
+
+    /// Queue a signal to be delivered to the current thread when it resumes
+    pub async fn queue_signal(&self, session_id: &str, signal: &str) -> AppResult<()> {
+        let command = MiCommand::queue_signal(signal);
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(())
+    }
+
+    /// Create a variable object for an expression in the current frame
+    pub async fn create_var_object(
+        &self,
+        session_id: &str,
+        expression: &str,
+    ) -> AppResult<VarObject> {
+        let command = MiCommand::var_create(None, expression, None);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        let var_object: VarObject = serde_json::from_value(response.results)?;
+
+        let handle = self.get_handle(session_id).await?;
+        handle.var_objects.lock().await.insert(var_object.name.clone());
+        Ok(var_object)
+    }
+
+    /// List the children of a variable object, optionally in the range
+    /// [from, to)
+    pub async fn list_var_children(
+        &self,
+        session_id: &str,
+        name: &str,
+        print_values: bool,
+        from: Option<u64>,
+        to: Option<u64>,
+    ) -> AppResult<VarChildren> {
+        let range = match (from, to) {
+            (None, None) => None,
+            (from, to) => Some(from.unwrap_or(0)..to.unwrap_or(u64::from(u32::MAX))),
+        };
+        let command = MiCommand::var_list_children(name, print_values, range);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(serde_json::from_value(response.results)?)
+    }
+
+    /// Update a variable object and its children, or all the variable objects,
+    /// and return those which changed
+    pub async fn update_var_objects(
+        &self,
+        session_id: &str,
+        name: Option<&str>,
+    ) -> AppResult<Vec<VarChange>> {
+        let command = MiCommand::var_update(name, PrintValue::AllValues);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(serde_json::from_value(
+            response
+                .results
+                .get("changelist")
+                .ok_or(AppError::NotFound("expect changelist in result".to_string()))?
+                .to_owned(),
+        )?)
+    }
+
+    /// Assign the value of an expression to a variable object and return the
+    /// new value
+    pub async fn assign_var_object(
+        &self,
+        session_id: &str,
+        name: &str,
+        expression: &str,
+    ) -> AppResult<String> {
+        let command = MiCommand::var_assign(name, expression);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        get_string(&response.results, "value")
+    }
 
-        Ok(response.results.to_string())
+    /// Set the display format of a variable object and return the formatted
+    /// value
+    pub async fn set_var_format(
+        &self,
+        session_id: &str,
+        name: &str,
+        format: VarFormat,
+    ) -> AppResult<String> {
+        let command = MiCommand::var_set_format(name, format);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        get_string(&response.results, "value")
+    }
+
+    /// Delete a variable object and its children
+    pub async fn delete_var_object(&self, session_id: &str, name: &str) -> AppResult<()> {
+        let command = MiCommand::var_delete(name, false);
+        check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+
+        let handle = self.get_handle(session_id).await?;
+        handle.var_objects.lock().await.remove(name);
+        Ok(())
+    }
+
+    /// Evaluate an expression, optionally in another thread or frame and with
+    /// a display format
+    pub async fn evaluate_expression(
+        &self,
+        session_id: &str,
+        expression: &str,
+        thread: Option<usize>,
+        frame: Option<usize>,
+        format: Option<VarFormat>,
+    ) -> AppResult<Evaluation> {
+        // The expression is evaluated only once, whatis skips its side effects
+        let command = match format {
+            Some(format) => MiCommand::output_expression(expression, format),
+            None => MiCommand::data_evaluate_expression(expression),
+        }
+        .thread_frame(thread, frame);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        let value = match format {
+            Some(_) => response.console.trim_end().to_string(),
+            None => get_string(&response.results, "value")?,
+        };
+
+        let command = MiCommand::whatis(expression).thread_frame(thread, frame);
+        let r#type = match self.send_command_with_timeout(session_id, &command).await {
+            Ok(response) => response.console.trim().strip_prefix("type = ").map(|t| t.to_string()),
+            Err(e) => {
+                warn!("Failed to get the type of {}: {}", expression, e);
+                None
+            }
+        };
+
+        Ok(Evaluation { expression: expression.to_string(), value, r#type })
+    }
+
+    /// Modify variable value and return the new value
+    pub async fn modify_variable(
+        &self,
+        session_id: &str,
+        variable: &str,
+        value: &str,
+    ) -> AppResult<Evaluation> {
+        let var_object = self.create_temp_var_object(session_id, variable, None, None).await?;
+        let value = self.assign_var_object(session_id, &var_object.name, value).await;
+        self.delete_temp_var_object(session_id, &var_object.name).await;
+
+        Ok(Evaluation {
+            expression: variable.to_string(),
+            value: value?,
+            r#type: var_object.r#type,
+        })
+    }
+
+    /// Create a variable object which is not tracked for the session, it must
+    /// be deleted by the caller
+    async fn create_temp_var_object(
+        &self,
+        session_id: &str,
+        expression: &str,
+        thread: Option<usize>,
+        frame: Option<usize>,
+    ) -> AppResult<VarObject> {
+        let command = MiCommand::var_create(None, expression, None).thread_frame(thread, frame);
+        let response = check_error(self.send_command_with_timeout(session_id, &command).await?)?;
+        Ok(serde_json::from_value(response.results)?)
+    }
+
+    async fn delete_temp_var_object(&self, session_id: &str, name: &str) {
+        let command = MiCommand::var_delete(name, false);
+        if let Err(e) =
+            self.send_command_with_timeout(session_id, &command).await.and_then(check_error)
+        {
+            warn!("Failed to delete variable object {}: {}", name, e);
+        }
+    }
+}
+
+/// Track the session status from the async records, the stop event is the
+/// parsed `*stopped` record if any
+fn update_session_status(
+    session: &mut GDBSession,
+    class: &AsyncClass,
+    results: &Value,
+    event: Option<&StopEvent>,
+) {
+    match class {
+        AsyncClass::Running => {
+            session.status = GDBSessionStatus::Running;
+            session.stop_reason = None;
+        }
+        AsyncClass::Stopped => {
+            let Some(event) = event else {
+                session.status = GDBSessionStatus::Stopped;
+                return;
+            };
+            session.stop_reason = event.reason.clone();
+            if event.thread_id.is_some() {
+                session.current_thread = event.thread_id.clone();
+            }
+            match event.reason {
+                Some(StopReason::Exited) => {
+                    session.status = GDBSessionStatus::Exited;
+                    session.exit_code = event.exit_code.clone();
+                }
+                Some(StopReason::ExitedNormally) => {
+                    session.status = GDBSessionStatus::Exited;
+                    session.exit_code = Some("0".to_string());
+                }
+                Some(StopReason::ExitedSignalled) => {
+                    session.status = GDBSessionStatus::Exited;
+                    session.exit_code = None;
+                }
+                _ => {
+                    session.status = GDBSessionStatus::Stopped;
+                    if event.frame.is_some() {
+                        session.frame = event.frame.clone();
+                    }
+                }
+            }
+        }
+        AsyncClass::Thread(ThreadEvent::GroupStarted) => {
+            session.exit_code = None;
+        }
+        AsyncClass::Thread(ThreadEvent::Selected) => {
+            session.current_thread = results.get("id").and_then(|id| id.as_str()).map(String::from);
+        }
+        AsyncClass::Thread(ThreadEvent::Exited)
+            if session.current_thread.as_deref()
+                == results.get("id").and_then(|id| id.as_str()) =>
+        {
+            session.current_thread = None;
+        }
+        AsyncClass::Thread(ThreadEvent::GroupExited) => {
+            session.current_thread = None;
+            session.record_method = None;
+            if let Some(exit_code) = results.get("exit-code").and_then(|c| c.as_str()) {
+                session.status = GDBSessionStatus::Exited;
+                session.exit_code = Some(exit_code.to_string());
+            } else if session.attached_pid.is_some() {
+                // No exit code when detaching
+                session.status = GDBSessionStatus::Detached;
+            } else {
+                session.status = GDBSessionStatus::Exited;
+            }
+            session.attached_pid = None;
+        }
+        AsyncClass::Record(RecordEvent::Started) => {
+            session.record_method =
+                results.get("method").and_then(|m| m.as_str()).map(String::from);
+        }
+        AsyncClass::Record(RecordEvent::Stopped) => {
+            session.record_method = None;
+            // The program continues live from the position in the log
+            if session.status == GDBSessionStatus::Replaying {
+                session.status = GDBSessionStatus::Stopped;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// What to disassemble
+pub enum DisassembleLocation<'a> {
+    /// The whole function, given by its name or an address in it
+    Function(&'a str),
+    /// Lines of a source file from the line, or the whole function containing
+    /// the line if the number of lines is not given
+    File(&'a Path, usize, Option<usize>),
+    /// The address range [start, end), both may be expressions
+    Range(&'a str, &'a str),
+    /// The given number of instructions before and after the program counter
+    AroundPc(usize),
+}
+
+/// Get a string field of the results
+fn get_string(results: &Value, key: &str) -> AppResult<String> {
+    results
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or(AppError::NotFound(format!("expect {} in result", key)))
+}
+
+/// Parse breakpoint numbers such as "1" or "2.1"
+fn parse_breakpoint_numbers(breakpoints: &[String]) -> AppResult<Vec<BreakPointNumber>> {
+    Ok(breakpoints.iter().map(|num| num.trim().parse()).collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Convert an error result record into an application error
+fn check_error(record: ResultRecord) -> AppResult<ResultRecord> {
+    if record.class == ResultClass::Error {
+        let msg = record
+            .results
+            .get("msg")
+            .and_then(|msg| msg.as_str())
+            .map(|msg| msg.to_string())
+            .unwrap_or_else(|| record.results.to_string());
+        return Err(AppError::GDBError(msg));
     }
+    Ok(record)
 }
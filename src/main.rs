@@ -62,6 +62,9 @@ impl FromStr for TransportType {
 pub static TRANSPORT: LazyLock<Mutex<Option<Arc<Box<dyn Transport>>>>> =
     LazyLock::new(|| Mutex::new(None));
 
+/// TUI state, also filled by the tools
+static APP: LazyLock<Arc<Mutex<App>>> = LazyLock::new(|| Arc::new(Mutex::new(App::default())));
+
 fn resolve_home(path: &str) -> Option<PathBuf> {
     if path.starts_with("~/") {
         if let Ok(home) = env::var("HOME") {
@@ -165,7 +168,7 @@ struct App {
     /// Saved output such as (gdb) or > from gdb
     stream_output_prompt: String,
     /// Register TUI
-    register_changed: Vec<u8>,
+    register_changed: Vec<usize>,
     registers: Vec<TrackedRegister>,
     /// Saved Stack
     stack: BTreeMap<u64, ResolveSymbol>,
@@ -250,7 +253,7 @@ async fn main() -> Result<(), AppError> {
 
     info!("Starting MCP GDB Server on port {}", config.server_port);
 
-    let app = Arc::new(Mutex::new(Default::default()));
+    let app = APP.clone();
 
     // Initialize terminal
     let ui_handle = if args.enable_tui {
@@ -609,18 +612,75 @@ fn register_tools(builder: ServerProtocolBuilder) -> ServerProtocolBuilder {
         .register_tool(tools::GetSessionTool::tool(), tools::GetSessionTool::call())
         .register_tool(tools::GetAllSessionsTool::tool(), tools::GetAllSessionsTool::call())
         .register_tool(tools::CloseSessionTool::tool(), tools::CloseSessionTool::call())
+        .register_tool(tools::ReadOutputTool::tool(), tools::ReadOutputTool::call())
+        .register_tool(tools::ExecuteCliTool::tool(), tools::ExecuteCliTool::call())
+        .register_tool(tools::ExecuteMiTool::tool(), tools::ExecuteMiTool::call())
         .register_tool(tools::StartDebuggingTool::tool(), tools::StartDebuggingTool::call())
         .register_tool(tools::StopDebuggingTool::tool(), tools::StopDebuggingTool::call())
+        .register_tool(tools::HandleSignalTool::tool(), tools::HandleSignalTool::call())
+        .register_tool(tools::SendSignalTool::tool(), tools::SendSignalTool::call())
+        .register_tool(tools::QueueSignalTool::tool(), tools::QueueSignalTool::call())
+        .register_tool(tools::ConnectRemoteTool::tool(), tools::ConnectRemoteTool::call())
+        .register_tool(tools::PutRemoteFileTool::tool(), tools::PutRemoteFileTool::call())
+        .register_tool(tools::GetRemoteFileTool::tool(), tools::GetRemoteFileTool::call())
+        .register_tool(tools::AttachProcessTool::tool(), tools::AttachProcessTool::call())
+        .register_tool(tools::DetachProcessTool::tool(), tools::DetachProcessTool::call())
+        .register_tool(tools::ListProcessesTool::tool(), tools::ListProcessesTool::call())
         .register_tool(tools::GetBreakpointsTool::tool(), tools::GetBreakpointsTool::call())
         .register_tool(tools::SetBreakpointTool::tool(), tools::SetBreakpointTool::call())
+        .register_tool(tools::SetWatchpointTool::tool(), tools::SetWatchpointTool::call())
+        .register_tool(tools::SetCatchpointTool::tool(), tools::SetCatchpointTool::call())
         .register_tool(tools::DeleteBreakpointTool::tool(), tools::DeleteBreakpointTool::call())
+        .register_tool(tools::EnableBreakpointsTool::tool(), tools::EnableBreakpointsTool::call())
+        .register_tool(tools::DisableBreakpointsTool::tool(), tools::DisableBreakpointsTool::call())
+        .register_tool(
+            tools::SetBreakpointConditionTool::tool(),
+            tools::SetBreakpointConditionTool::call(),
+        )
+        .register_tool(
+            tools::SetBreakpointIgnoreCountTool::tool(),
+            tools::SetBreakpointIgnoreCountTool::call(),
+        )
+        .register_tool(
+            tools::SetBreakpointCommandsTool::tool(),
+            tools::SetBreakpointCommandsTool::call(),
+        )
+        .register_tool(tools::ListThreadsTool::tool(), tools::ListThreadsTool::call())
+        .register_tool(tools::SelectThreadTool::tool(), tools::SelectThreadTool::call())
         .register_tool(tools::GetStackFramesTool::tool(), tools::GetStackFramesTool::call())
+        .register_tool(tools::SelectFrameTool::tool(), tools::SelectFrameTool::call())
+        .register_tool(tools::GetFrameInfoTool::tool(), tools::GetFrameInfoTool::call())
         .register_tool(tools::GetLocalVariablesTool::tool(), tools::GetLocalVariablesTool::call())
+        .register_tool(tools::VarCreateTool::tool(), tools::VarCreateTool::call())
+        .register_tool(tools::VarListChildrenTool::tool(), tools::VarListChildrenTool::call())
+        .register_tool(tools::VarUpdateTool::tool(), tools::VarUpdateTool::call())
+        .register_tool(tools::VarAssignTool::tool(), tools::VarAssignTool::call())
+        .register_tool(tools::VarSetFormatTool::tool(), tools::VarSetFormatTool::call())
+        .register_tool(tools::VarDeleteTool::tool(), tools::VarDeleteTool::call())
         .register_tool(tools::ContinueExecutionTool::tool(), tools::ContinueExecutionTool::call())
         .register_tool(tools::StepExecutionTool::tool(), tools::StepExecutionTool::call())
         .register_tool(tools::NextExecutionTool::tool(), tools::NextExecutionTool::call())
+        .register_tool(tools::StepInstructionTool::tool(), tools::StepInstructionTool::call())
+        .register_tool(tools::NextInstructionTool::tool(), tools::NextInstructionTool::call())
+        .register_tool(tools::FinishExecutionTool::tool(), tools::FinishExecutionTool::call())
+        .register_tool(tools::UntilExecutionTool::tool(), tools::UntilExecutionTool::call())
+        .register_tool(tools::AdvanceExecutionTool::tool(), tools::AdvanceExecutionTool::call())
+        .register_tool(tools::JumpExecutionTool::tool(), tools::JumpExecutionTool::call())
+        .register_tool(tools::ReturnFromFunctionTool::tool(), tools::ReturnFromFunctionTool::call())
+        .register_tool(tools::StartRecordingTool::tool(), tools::StartRecordingTool::call())
+        .register_tool(tools::StopRecordingTool::tool(), tools::StopRecordingTool::call())
+        .register_tool(tools::GetRecordStatusTool::tool(), tools::GetRecordStatusTool::call())
+        .register_tool(tools::ReverseStepTool::tool(), tools::ReverseStepTool::call())
+        .register_tool(tools::ReverseNextTool::tool(), tools::ReverseNextTool::call())
+        .register_tool(tools::ReverseContinueTool::tool(), tools::ReverseContinueTool::call())
+        .register_tool(tools::ReverseFinishTool::tool(), tools::ReverseFinishTool::call())
         .register_tool(tools::GetRegistersTool::tool(), tools::GetRegistersTool::call())
         .register_tool(tools::GetRegisterNamesTool::tool(), tools::GetRegisterNamesTool::call())
         .register_tool(tools::ReadMemoryTool::tool(), tools::ReadMemoryTool::call())
+        .register_tool(tools::WriteMemoryTool::tool(), tools::WriteMemoryTool::call())
+        .register_tool(tools::SetRegisterTool::tool(), tools::SetRegisterTool::call())
+        .register_tool(tools::DisassembleTool::tool(), tools::DisassembleTool::call())
+        .register_tool(tools::AnalyzeCoreTool::tool(), tools::AnalyzeCoreTool::call())
+        .register_tool(tools::EvaluateExpressionTool::tool(), tools::EvaluateExpressionTool::call())
         .register_tool(tools::ModifyVariableTool::tool(), tools::ModifyVariableTool::call())
 }
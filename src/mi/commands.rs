@@ -1,9 +1,11 @@
+use std::borrow::Cow;
 use std::ffi::OsString;
 use std::fmt;
 use std::io::Error;
 use std::path::Path;
 use std::str::FromStr;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize, de};
 use tokio::io::AsyncWriteExt;
 use tracing::info;
@@ -12,11 +14,12 @@ use crate::models::PrintValue;
 
 #[derive(Debug, Clone, Default)]
 pub struct MiCommand {
-    pub operation: &'static str,
+    pub operation: Cow<'static, str>,
     pub options: Option<Vec<OsString>>,
     pub parameters: Option<Vec<OsString>>,
 }
 
+#[derive(Debug, Clone, Copy)]
 pub enum DisassembleMode {
     DisassemblyOnly = 0,
     DisassemblyWithRawOpcodes = 2,
@@ -32,7 +35,176 @@ pub enum WatchMode {
     Access,
 }
 
+impl FromStr for WatchMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "read" => WatchMode::Read,
+            "write" => WatchMode::Write,
+            "access" => WatchMode::Access,
+            _ => return Err(format!("Invalid watch mode: {}", s)),
+        })
+    }
+}
+
+/// Display format of a variable object
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarFormat {
+    Binary,
+    Decimal,
+    Hexadecimal,
+    Octal,
+    Natural,
+    ZeroHexadecimal,
+}
+
+impl FromStr for VarFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Also accept the letters of the print command formats
+        Ok(match s {
+            "binary" | "t" => VarFormat::Binary,
+            "decimal" | "d" => VarFormat::Decimal,
+            "hexadecimal" | "x" => VarFormat::Hexadecimal,
+            "octal" | "o" => VarFormat::Octal,
+            "natural" | "N" => VarFormat::Natural,
+            "zero-hexadecimal" | "z" => VarFormat::ZeroHexadecimal,
+            _ => return Err(format!("Invalid variable format: {}", s)),
+        })
+    }
+}
+
+impl fmt::Display for VarFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VarFormat::Binary => write!(f, "binary"),
+            VarFormat::Decimal => write!(f, "decimal"),
+            VarFormat::Hexadecimal => write!(f, "hexadecimal"),
+            VarFormat::Octal => write!(f, "octal"),
+            VarFormat::Natural => write!(f, "natural"),
+            VarFormat::ZeroHexadecimal => write!(f, "zero-hexadecimal"),
+        }
+    }
+}
+
+impl VarFormat {
+    /// Letter of the format in the print and output commands, none for natural
+    pub fn letter(&self) -> Option<char> {
+        match self {
+            VarFormat::Binary => Some('t'),
+            VarFormat::Decimal => Some('d'),
+            VarFormat::Hexadecimal => Some('x'),
+            VarFormat::Octal => Some('o'),
+            VarFormat::Natural => None,
+            VarFormat::ZeroHexadecimal => Some('z'),
+        }
+    }
+}
+
+/// Method of the process record used for reverse debugging
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordMethod {
+    /// Record every instruction, slow but supported by most targets
+    Full,
+    /// Branch tracing by the hardware, in the format preferred by GDB
+    Btrace,
+    /// Branch tracing with Intel Branch Trace Store
+    Bts,
+    /// Branch tracing with Intel Processor Trace
+    Pt,
+}
+
+impl FromStr for RecordMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "full" => RecordMethod::Full,
+            "btrace" => RecordMethod::Btrace,
+            "bts" => RecordMethod::Bts,
+            "pt" => RecordMethod::Pt,
+            _ => return Err(format!("Invalid record method: {}", s)),
+        })
+    }
+}
+
+impl fmt::Display for RecordMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecordMethod::Full => write!(f, "full"),
+            RecordMethod::Btrace => write!(f, "btrace"),
+            RecordMethod::Bts => write!(f, "btrace bts"),
+            RecordMethod::Pt => write!(f, "btrace pt"),
+        }
+    }
+}
+
+/// Event stopping the program at a catchpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatchEvent {
+    /// A C++ exception is thrown
+    Throw,
+    /// A C++ exception is caught
+    Catch,
+    /// A C++ exception is rethrown
+    Rethrow,
+    /// A shared library is loaded
+    Load,
+    /// A shared library is unloaded
+    Unload,
+    /// A system call is entered or returns
+    Syscall,
+    /// A signal is delivered
+    Signal,
+    /// The program forks
+    Fork,
+    /// The program vforks
+    Vfork,
+    /// The program calls exec
+    Exec,
+}
+
+impl FromStr for CatchEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "throw" => CatchEvent::Throw,
+            "catch" => CatchEvent::Catch,
+            "rethrow" => CatchEvent::Rethrow,
+            "load" => CatchEvent::Load,
+            "unload" => CatchEvent::Unload,
+            "syscall" => CatchEvent::Syscall,
+            "signal" => CatchEvent::Signal,
+            "fork" => CatchEvent::Fork,
+            "vfork" => CatchEvent::Vfork,
+            "exec" => CatchEvent::Exec,
+            _ => return Err(format!("Invalid catchpoint event: {}", s)),
+        })
+    }
+}
+
+impl fmt::Display for CatchEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CatchEvent::Throw => write!(f, "throw"),
+            CatchEvent::Catch => write!(f, "catch"),
+            CatchEvent::Rethrow => write!(f, "rethrow"),
+            CatchEvent::Load => write!(f, "load"),
+            CatchEvent::Unload => write!(f, "unload"),
+            CatchEvent::Syscall => write!(f, "syscall"),
+            CatchEvent::Signal => write!(f, "signal"),
+            CatchEvent::Fork => write!(f, "fork"),
+            CatchEvent::Vfork => write!(f, "vfork"),
+            CatchEvent::Exec => write!(f, "exec"),
+        }
+    }
+}
+
 /// Register format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RegisterFormat {
     Binary,
     Hex,
@@ -46,13 +218,14 @@ impl FromStr for RegisterFormat {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Also accept the letters of -data-list-register-values
         Ok(match s {
-            "b" => RegisterFormat::Binary,
-            "x" => RegisterFormat::Hex,
-            "d" => RegisterFormat::Decimal,
-            "o" => RegisterFormat::Octal,
-            "r" => RegisterFormat::Raw,
-            "N" => RegisterFormat::Natural,
+            "binary" | "t" => RegisterFormat::Binary,
+            "hex" | "hexadecimal" | "x" => RegisterFormat::Hex,
+            "decimal" | "d" => RegisterFormat::Decimal,
+            "octal" | "o" => RegisterFormat::Octal,
+            "raw" | "r" => RegisterFormat::Raw,
+            "natural" | "N" => RegisterFormat::Natural,
             _ => return Err(format!("Invalid register format: {}", s)),
         })
     }
@@ -61,7 +234,7 @@ impl FromStr for RegisterFormat {
 impl fmt::Display for RegisterFormat {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            RegisterFormat::Binary => write!(f, "b"),
+            RegisterFormat::Binary => write!(f, "t"),
             RegisterFormat::Hex => write!(f, "x"),
             RegisterFormat::Decimal => write!(f, "d"),
             RegisterFormat::Octal => write!(f, "o"),
@@ -73,8 +246,34 @@ impl fmt::Display for RegisterFormat {
 
 pub enum BreakPointLocation<'a> {
     Address(usize),
-    Function(&'a Path, &'a str),
+    /// Function, optionally restricted to a source file
+    Function(Option<&'a Path>, &'a str),
     Line(&'a Path, usize),
+    /// Location passed to GDB as is, e.g. "file.c:func", "*0x1234" or
+    /// "--source file.c --line 10"
+    Linespec(&'a str),
+}
+
+/// Options of the break-insert command, also the options of the
+/// set_breakpoint tool
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct BreakPointOptions {
+    /// Only stop if the condition is true (-c)
+    pub condition: Option<String>,
+    /// Number of times the breakpoint is ignored (-i)
+    pub ignore_count: Option<usize>,
+    /// Delete the breakpoint once hit (-t)
+    pub temporary: bool,
+    /// Insert a hardware breakpoint (-h)
+    pub hardware: bool,
+    /// Create the breakpoint disabled (-d)
+    pub disabled: bool,
+    /// Create a pending breakpoint if the location is not found, e.g. in a
+    /// shared library not loaded yet (-f)
+    pub pending: bool,
+    /// Only stop in the thread of this ID (-p)
+    pub thread: Option<usize>,
 }
 
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Serialize)]
@@ -83,23 +282,28 @@ pub struct BreakPointNumber {
     pub minor: Option<usize>,
 }
 
+impl FromStr for BreakPointNumber {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(dot_pos) = s.find('.') {
+            Ok(BreakPointNumber {
+                major: s[..dot_pos].parse::<usize>()?,
+                minor: Some(s[dot_pos + 1..].parse::<usize>()?),
+            })
+        } else {
+            Ok(BreakPointNumber { major: s.parse::<usize>()?, minor: None })
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for BreakPointNumber {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         let s: String = serde::Deserialize::deserialize(deserializer)?;
-        if let Some(dot_pos) = s.find('.') {
-            Ok(BreakPointNumber {
-                major: s[..dot_pos].parse::<usize>().map_err(de::Error::custom)?,
-                minor: Some(s[dot_pos + 1..].parse::<usize>().map_err(de::Error::custom)?),
-            })
-        } else {
-            Ok(BreakPointNumber {
-                major: s.parse::<usize>().map_err(de::Error::custom)?,
-                minor: None,
-            })
-        }
+        s.parse().map_err(de::Error::custom)
     }
 }
 
@@ -119,8 +323,8 @@ fn escape_command(input: &str) -> String {
         match c {
             '\\' => output.push_str("\\\\"),
             '\"' => output.push_str("\\\""),
-            '\r' => output.push_str("\\\r"),
-            '\n' => output.push_str("\\\n"),
+            '\r' => output.push_str("\\r"),
+            '\n' => output.push_str("\\n"),
             other => output.push(other),
         }
     }
@@ -162,12 +366,48 @@ impl MiCommand {
         Ok(())
     }
 
+    /// Arbitrary MI command, the operation may be given with or without the
+    /// leading dash. Options are separated from the parameters by "--".
+    pub fn raw(
+        operation: &str,
+        options: Option<Vec<String>>,
+        parameters: Option<Vec<String>>,
+    ) -> MiCommand {
+        MiCommand {
+            operation: operation.trim().trim_start_matches('-').to_string().into(),
+            options: options.map(|o| o.into_iter().map(OsString::from).collect()),
+            parameters: parameters.map(|p| p.into_iter().map(OsString::from).collect()),
+        }
+    }
+
+    /// Apply the command to the given thread and frame instead of the
+    /// selected ones, with the --thread and --frame global options
+    pub fn thread_frame(mut self, thread: Option<usize>, frame: Option<usize>) -> MiCommand {
+        let mut global = Vec::<OsString>::new();
+        if let Some(thread) = thread {
+            global.push("--thread".into());
+            global.push(thread.to_string().into());
+        }
+        if let Some(frame) = frame {
+            global.push("--frame".into());
+            global.push(frame.to_string().into());
+        }
+        // The global options must come first, before "--" if any
+        let args = match (&mut self.options, &mut self.parameters) {
+            (Some(options), _) => options,
+            (None, Some(parameters)) => parameters,
+            (None, None) => self.parameters.insert(vec![]),
+        };
+        args.splice(0..0, global);
+        self
+    }
+
     pub fn interpreter_exec<S1: Into<OsString>, S2: Into<OsString>>(
         interpreter: S1,
         command: S2,
     ) -> MiCommand {
         MiCommand {
-            operation: "interpreter-exec",
+            operation: "interpreter-exec".into(),
             options: Some(vec![interpreter.into(), command.into()]),
             parameters: None,
         }
@@ -184,7 +424,7 @@ impl MiCommand {
         mode: DisassembleMode,
     ) -> MiCommand {
         MiCommand {
-            operation: "data-disassemble",
+            operation: "data-disassemble".into(),
             options: Some(vec![
                 OsString::from("-f"),
                 OsString::from(file.as_ref()),
@@ -197,43 +437,100 @@ impl MiCommand {
         }
     }
 
-    pub fn data_disassemble_address(
-        start_addr: usize,
-        end_addr: usize,
-        mode: DisassembleMode,
-    ) -> MiCommand {
+    /// Disassemble the address range [start, end), both may be expressions,
+    /// e.g. "$pc" or "main + 16"
+    pub fn data_disassemble_address(start: &str, end: &str, mode: DisassembleMode) -> MiCommand {
         MiCommand {
-            operation: "data-disassemble",
+            operation: "data-disassemble".into(),
             options: Some(vec![
                 OsString::from("-s"),
-                OsString::from(start_addr.to_string()),
+                OsString::from(escape_command(start)),
                 OsString::from("-e"),
-                OsString::from(end_addr.to_string()),
+                OsString::from(escape_command(end)),
             ]),
             parameters: Some(vec![OsString::from((mode as u8).to_string())]),
         }
     }
 
-    pub fn data_evaluate_expression(expression: String) -> MiCommand {
+    /// Disassemble the whole function containing the address, which may be an
+    /// expression or a function name
+    pub fn data_disassemble_function(address: &str, mode: DisassembleMode) -> MiCommand {
         MiCommand {
-            operation: "data-evaluate-expression",
-            options: Some(vec![OsString::from(format!("\"{}\"", expression))]), /* TODO: maybe we need to quote existing " in expression. Is this even possible? */
-            parameters: None,
+            operation: "data-disassemble".into(),
+            options: Some(vec![OsString::from("-a"), OsString::from(escape_command(address))]),
+            parameters: Some(vec![OsString::from((mode as u8).to_string())]),
         }
     }
 
-    pub fn insert_breakpoint(location: BreakPointLocation) -> MiCommand {
+    pub fn data_evaluate_expression(expression: &str) -> MiCommand {
         MiCommand {
-            operation: "break-insert",
+            operation: "data-evaluate-expression".into(),
+            options: None,
+            parameters: Some(vec![escape_command(expression).into()]),
+        }
+    }
+
+    /// Print the value of an expression in a format, which
+    /// -data-evaluate-expression has no option for. Unlike print, output does
+    /// not record the value in the value history
+    pub fn output_expression(expression: &str, format: VarFormat) -> MiCommand {
+        match format.letter() {
+            Some(letter) => Self::cli_exec(&format!("output/{} {}", letter, expression)),
+            None => Self::cli_exec(&format!("output {}", expression)),
+        }
+    }
+
+    /// Print the type of an expression, without running its side effects
+    pub fn whatis(expression: &str) -> MiCommand {
+        Self::cli_exec(&format!("whatis {}", expression))
+    }
+
+    pub fn insert_breakpoint(
+        location: BreakPointLocation,
+        options: &BreakPointOptions,
+    ) -> MiCommand {
+        let mut flags = Vec::<OsString>::new();
+        if options.temporary {
+            flags.push("-t".into());
+        }
+        if options.hardware {
+            flags.push("-h".into());
+        }
+        if options.pending {
+            flags.push("-f".into());
+        }
+        if options.disabled {
+            flags.push("-d".into());
+        }
+        if let Some(condition) = &options.condition {
+            flags.push("-c".into());
+            flags.push(escape_command(condition).into());
+        }
+        if let Some(ignore_count) = options.ignore_count {
+            flags.push("-i".into());
+            flags.push(ignore_count.to_string().into());
+        }
+        if let Some(thread) = options.thread {
+            flags.push("-p".into());
+            flags.push(thread.to_string().into());
+        }
+        MiCommand {
+            operation: "break-insert".into(),
             options: match location {
                 BreakPointLocation::Address(addr) => {
-                    Some(vec![OsString::from(format!("*0x{:x}", addr))])
+                    flags.push(OsString::from(format!("*0x{:x}", addr)));
+                    Some(flags)
+                }
+                BreakPointLocation::Function(None, func_name) => {
+                    flags.push(func_name.into());
+                    Some(flags)
                 }
-                BreakPointLocation::Function(path, func_name) => {
+                BreakPointLocation::Function(Some(path), func_name) => {
                     let mut ret = OsString::from(path);
                     ret.push(":");
                     ret.push(func_name);
-                    Some(vec![ret])
+                    flags.push(ret);
+                    Some(flags)
 
                     // Not available in old gdb(mi) versions
                     //vec![
@@ -247,7 +544,8 @@ impl MiCommand {
                     let mut ret = OsString::from(path);
                     ret.push(":");
                     ret.push(line_number.to_string());
-                    Some(vec![ret])
+                    flags.push(ret);
+                    Some(flags)
 
                     // Not available in old gdb(mi) versions
                     //vec![
@@ -257,25 +555,116 @@ impl MiCommand {
                     //OsString::from(format!("{}", line_number)),
                     //],
                 }
+                BreakPointLocation::Linespec(location) => {
+                    flags.push(location.into());
+                    Some(flags)
+                }
             },
             parameters: None,
         }
     }
 
+    /// Set a catchpoint. The argument is a regular expression matching the
+    /// exception type or the library name, the syscalls or the signals to
+    /// catch, separated by spaces. Syscall, signal, fork, vfork and exec
+    /// catchpoints have no MI command
+    pub fn catch(event: CatchEvent, argument: Option<&str>, temporary: bool) -> MiCommand {
+        let mut options = Vec::<OsString>::new();
+        if temporary {
+            options.push("-t".into());
+        }
+        match event {
+            CatchEvent::Throw | CatchEvent::Catch | CatchEvent::Rethrow => {
+                if let Some(regexp) = argument {
+                    options.push("-r".into());
+                    options.push(escape_command(regexp).into());
+                }
+                MiCommand {
+                    operation: format!("catch-{}", event).into(),
+                    options: Some(options),
+                    parameters: None,
+                }
+            }
+            CatchEvent::Load | CatchEvent::Unload => MiCommand {
+                operation: format!("catch-{}", event).into(),
+                options: Some(options),
+                parameters: Some(vec![escape_command(argument.unwrap_or(".*")).into()]),
+            },
+            _ => {
+                let catch = if temporary { "tcatch" } else { "catch" };
+                let command = match argument {
+                    Some(argument) => format!("{} {} {}", catch, event, argument),
+                    None => format!("{} {}", catch, event),
+                };
+                Self::cli_exec(&command)
+            }
+        }
+    }
+
     pub fn delete_breakpoints(breakpoint_numbers: Vec<BreakPointNumber>) -> MiCommand {
         //GDB is broken: see http://sourceware-org.1504.n7.nabble.com/Bug-breakpoints-20133-New-unable-to-delete-a-sub-breakpoint-td396197.html
         let mut options = breakpoint_numbers;
         options.sort_by_key(|n| n.major);
         options.dedup();
         MiCommand {
-            operation: "break-delete",
+            operation: "break-delete".into(),
             options: Some(options.iter().map(|n| n.to_string().into()).collect()),
             parameters: None,
         }
     }
 
+    pub fn enable_breakpoints(breakpoint_numbers: &[BreakPointNumber]) -> MiCommand {
+        MiCommand {
+            operation: "break-enable".into(),
+            options: None,
+            parameters: Some(breakpoint_numbers.iter().map(|n| n.to_string().into()).collect()),
+        }
+    }
+
+    pub fn disable_breakpoints(breakpoint_numbers: &[BreakPointNumber]) -> MiCommand {
+        MiCommand {
+            operation: "break-disable".into(),
+            options: None,
+            parameters: Some(breakpoint_numbers.iter().map(|n| n.to_string().into()).collect()),
+        }
+    }
+
+    /// Set the condition of a breakpoint, or remove it if None
+    pub fn breakpoint_condition(number: BreakPointNumber, condition: Option<&str>) -> MiCommand {
+        let mut parameters = vec![number.to_string().into()];
+        if let Some(condition) = condition {
+            parameters.push(escape_command(condition).into());
+        }
+        MiCommand {
+            operation: "break-condition".into(),
+            options: None,
+            parameters: Some(parameters),
+        }
+    }
+
+    /// Ignore the next `count` hits of a breakpoint
+    pub fn breakpoint_after(number: BreakPointNumber, count: usize) -> MiCommand {
+        MiCommand {
+            operation: "break-after".into(),
+            options: None,
+            parameters: Some(vec![number.to_string().into(), count.to_string().into()]),
+        }
+    }
+
+    /// Set the CLI commands executed when a breakpoint is hit, an empty list
+    /// clears them
+    pub fn breakpoint_commands(number: BreakPointNumber, commands: &[String]) -> MiCommand {
+        let mut parameters = vec![number.to_string().into()];
+        parameters.extend(commands.iter().map(|command| escape_command(command).into()));
+        MiCommand {
+            operation: "break-commands".into(),
+            options: None,
+            parameters: Some(parameters),
+        }
+    }
+
     pub fn breakpoints_list() -> MiCommand {
-        MiCommand { operation: "break-list", ..Default::default() }
+        MiCommand { operation: "break-list".into(), ..Default::default() }
     }
 
     pub fn insert_watchpoint(expression: &str, mode: WatchMode) -> MiCommand {
@@ -284,33 +673,140 @@ impl MiCommand {
             WatchMode::Read => Some(vec!["-r".into()]),
             WatchMode::Access => Some(vec!["-a".into()]),
         };
-        MiCommand { operation: "break-watch", options, parameters: Some(vec![expression.into()]) }
+        MiCommand {
+            operation: "break-watch".into(),
+            options,
+            parameters: Some(vec![escape_command(expression).into()]),
+        }
     }
 
     pub fn environment_pwd() -> MiCommand {
-        MiCommand { operation: "environment-pwd", ..Default::default() }
+        MiCommand { operation: "environment-pwd".into(), ..Default::default() }
     }
 
     // Be aware: This does not seem to always interrupt execution.
     // Use gdb.interrupt_execution instead.
     pub fn exec_interrupt() -> MiCommand {
-        MiCommand { operation: "exec-interrupt", ..Default::default() }
+        MiCommand { operation: "exec-interrupt".into(), ..Default::default() }
     }
 
     pub fn exec_run() -> MiCommand {
-        MiCommand { operation: "exec-run", ..Default::default() }
+        MiCommand { operation: "exec-run".into(), ..Default::default() }
     }
 
     pub fn exec_continue() -> MiCommand {
-        MiCommand { operation: "exec-continue", ..Default::default() }
+        MiCommand { operation: "exec-continue".into(), ..Default::default() }
     }
 
     pub fn exec_step() -> MiCommand {
-        MiCommand { operation: "exec-step", ..Default::default() }
+        MiCommand { operation: "exec-step".into(), ..Default::default() }
     }
 
     pub fn exec_next() -> MiCommand {
-        MiCommand { operation: "exec-next", ..Default::default() }
+        MiCommand { operation: "exec-next".into(), ..Default::default() }
+    }
+
+    pub fn exec_finish() -> MiCommand {
+        MiCommand { operation: "exec-finish".into(), ..Default::default() }
+    }
+
+    pub fn exec_step_instruction() -> MiCommand {
+        MiCommand { operation: "exec-step-instruction".into(), ..Default::default() }
+    }
+
+    pub fn exec_next_instruction() -> MiCommand {
+        MiCommand { operation: "exec-next-instruction".into(), ..Default::default() }
+    }
+
+    /// Run until a line greater than the current one or the location is
+    /// reached, or the current frame returns
+    pub fn exec_until(location: Option<&str>) -> MiCommand {
+        MiCommand {
+            operation: "exec-until".into(),
+            options: None,
+            parameters: location.map(|location| vec![escape_command(location).into()]),
+        }
+    }
+
+    /// Resume the program at the location
+    pub fn exec_jump(location: &str) -> MiCommand {
+        MiCommand {
+            operation: "exec-jump".into(),
+            options: None,
+            parameters: Some(vec![escape_command(location).into()]),
+        }
+    }
+
+    /// Make the current function return immediately, optionally with the
+    /// value of the expression, without executing the rest of it
+    pub fn exec_return(value: Option<&str>) -> MiCommand {
+        MiCommand {
+            operation: "exec-return".into(),
+            options: None,
+            parameters: value.map(|value| vec![escape_command(value).into()]),
+        }
+    }
+
+    /// Run until the location or the current frame returns, there is no MI
+    /// command for it
+    pub fn advance(location: &str) -> MiCommand {
+        Self::cli_exec(&format!("advance {}", location))
+    }
+
+    /// Repeat a step, next, stepi or nexti command
+    pub fn repeat(mut self, count: Option<usize>) -> MiCommand {
+        if let Some(count) = count {
+            self.parameters.get_or_insert_with(Vec::new).push(count.to_string().into());
+        }
+        self
+    }
+
+    /// Execute backwards, for the execution commands which support it. The
+    /// program must be recorded, see `record_start`
+    pub fn reverse(mut self) -> MiCommand {
+        self.options.get_or_insert_with(Vec::new).insert(0, "--reverse".into());
+        self
+    }
+
+    /// Whether the command executes backwards
+    pub fn is_reverse(&self) -> bool {
+        self.options.as_ref().is_some_and(|options| options.iter().any(|o| o == "--reverse"))
+    }
+
+    /// Start recording the program, there is no MI command for it
+    pub fn record_start(method: RecordMethod) -> MiCommand {
+        Self::cli_exec(&format!("record {}", method))
+    }
+
+    /// Stop recording the program and delete the execution log
+    pub fn record_stop() -> MiCommand {
+        Self::cli_exec("record stop")
+    }
+
+    /// Describe the active process record
+    pub fn record_info() -> MiCommand {
+        Self::cli_exec("info record")
+    }
+
+    /// Change how GDB handles a signal with actions such as "stop", "nostop",
+    /// "print", "noprint", "pass" or "nopass", or list how it is handled if
+    /// no action is given
+    pub fn handle_signal(signal: &str, actions: &[&str]) -> MiCommand {
+        if actions.is_empty() {
+            Self::cli_exec(&format!("info signals {}", signal))
+        } else {
+            Self::cli_exec(&format!("handle {} {}", signal, actions.join(" ")))
+        }
+    }
+
+    /// Resume the program with a signal
+    pub fn signal(signal: &str) -> MiCommand {
+        Self::cli_exec(&format!("signal {}", signal))
+    }
+
+    /// Queue a signal delivered to the current thread when it resumes
+    pub fn queue_signal(signal: &str) -> MiCommand {
+        Self::cli_exec(&format!("queue-signal {}", signal))
     }
 
     // Warning: This cannot be used to pass special characters like \n to gdb
@@ -318,35 +814,52 @@ impl MiCommand {
     // pass \n unescaped to gdb, and for "exec-arguments" gdb somehow does not
     // unescape these chars...
     pub fn exec_arguments(args: Vec<OsString>) -> MiCommand {
-        MiCommand { operation: "exec-arguments", options: Some(args), parameters: None }
+        MiCommand { operation: "exec-arguments".into(), options: Some(args), parameters: None }
     }
 
     pub fn exit() -> MiCommand {
-        MiCommand { operation: "gdb-exit", ..Default::default() }
+        MiCommand { operation: "gdb-exit".into(), ..Default::default() }
     }
 
     pub fn select_frame(frame_number: u64) -> MiCommand {
         MiCommand {
-            operation: "stack-select-frame",
+            operation: "stack-select-frame".into(),
             options: Some(vec![frame_number.to_string().into()]),
             parameters: None,
         }
     }
 
-    pub fn stack_info_frame(frame_number: Option<u64>) -> MiCommand {
-        MiCommand {
-            operation: "stack-info-frame",
-            options: if let Some(frame_number) = frame_number {
-                Some(vec![frame_number.to_string().into()])
-            } else {
-                None
-            },
-            parameters: None,
-        }
+    /// Info of the given frame of a thread, or of the selected frame if None
+    pub fn stack_info_frame(thread_frame: Option<(usize, usize)>) -> MiCommand {
+        // -stack-info-frame takes no arguments, the frame is given with --frame
+        // which is only valid along with --thread
+        let (thread, frame) = thread_frame.unzip();
+        MiCommand { operation: "stack-info-frame".into(), ..Default::default() }
+            .thread_frame(thread, frame)
     }
 
     pub fn stack_info_depth() -> MiCommand {
-        MiCommand { operation: "stack-info-depth", ..Default::default() }
+        MiCommand { operation: "stack-info-depth".into(), ..Default::default() }
+    }
+
+    /// Arguments of the frames between low_frame and high_frame, inclusive, or
+    /// of all frames
+    pub fn stack_list_arguments(
+        print_values: PrintValue,
+        low_frame: Option<usize>,
+        high_frame: Option<usize>,
+    ) -> MiCommand {
+        let mut parameters = vec![print_values.to_string().into()];
+        if low_frame.is_some() || high_frame.is_some() {
+            parameters.push(low_frame.unwrap_or(0).to_string().into());
+            // large enough number to include all frames, only existing frames will be shown
+            parameters.push(high_frame.unwrap_or(99999).to_string().into());
+        }
+        MiCommand {
+            operation: "stack-list-arguments".into(),
+            options: None,
+            parameters: Some(parameters),
+        }
     }
 
     pub fn stack_list_variables(
@@ -368,7 +881,11 @@ impl MiCommand {
         } else {
             parameters.push("--simple-values".into());
         }
-        MiCommand { operation: "stack-list-variables", options: None, parameters: Some(parameters) }
+        MiCommand {
+            operation: "stack-list-variables".into(),
+            options: None,
+            parameters: Some(parameters),
+        }
     }
 
     pub fn stack_list_frames(low_frame: Option<usize>, high_frame: Option<usize>) -> MiCommand {
@@ -390,12 +907,12 @@ impl MiCommand {
                 None
             }
         };
-        MiCommand { operation: "stack-list-frames", options, parameters: None }
+        MiCommand { operation: "stack-list-frames".into(), options, parameters: None }
     }
 
     pub fn thread_info(thread_id: Option<u64>) -> MiCommand {
         MiCommand {
-            operation: "thread-info",
+            operation: "thread-info".into(),
             options: if let Some(id) = thread_id {
                 Some(vec![id.to_string().into()])
             } else {
@@ -405,9 +922,17 @@ impl MiCommand {
         }
     }
 
+    pub fn thread_select(thread_id: usize) -> MiCommand {
+        MiCommand {
+            operation: "thread-select".into(),
+            options: None,
+            parameters: Some(vec![thread_id.to_string().into()]),
+        }
+    }
+
     pub fn file_exec_and_symbols(file: &Path) -> MiCommand {
         MiCommand {
-            operation: "file-exec-and-symbols",
+            operation: "file-exec-and-symbols".into(),
             options: Some(vec![file.into()]),
             parameters: None,
         }
@@ -415,21 +940,83 @@ impl MiCommand {
 
     pub fn file_symbol_file(file: Option<&Path>) -> MiCommand {
         MiCommand {
-            operation: "file-symbol-file",
+            operation: "file-symbol-file".into(),
             options: if let Some(file) = file { Some(vec![file.into()]) } else { None },
             parameters: None,
         }
     }
 
+    pub fn target_attach(pid: u32) -> MiCommand {
+        MiCommand {
+            operation: "target-attach".into(),
+            options: None,
+            parameters: Some(vec![pid.to_string().into()]),
+        }
+    }
+
+    /// Connect to a remote target, e.g. "localhost:1234" or "/dev/ttyUSB0"
+    pub fn target_select(target: &str, extended: bool) -> MiCommand {
+        MiCommand {
+            operation: "target-select".into(),
+            options: None,
+            parameters: Some(vec![
+                if extended { "extended-remote" } else { "remote" }.into(),
+                escape_command(target).into(),
+            ]),
+        }
+    }
+
+    /// Load a core dump as the target
+    pub fn target_select_core(core_file: &Path) -> MiCommand {
+        MiCommand {
+            operation: "target-select".into(),
+            options: None,
+            parameters: Some(vec![
+                "core".into(),
+                escape_command(&core_file.to_string_lossy()).into(),
+            ]),
+        }
+    }
+
+    pub fn target_file_put(host_file: &Path, target_file: &str) -> MiCommand {
+        MiCommand {
+            operation: "target-file-put".into(),
+            options: None,
+            parameters: Some(vec![
+                escape_command(&host_file.to_string_lossy()).into(),
+                escape_command(target_file).into(),
+            ]),
+        }
+    }
+
+    pub fn target_file_get(target_file: &str, host_file: &Path) -> MiCommand {
+        MiCommand {
+            operation: "target-file-get".into(),
+            options: None,
+            parameters: Some(vec![
+                escape_command(target_file).into(),
+                escape_command(&host_file.to_string_lossy()).into(),
+            ]),
+        }
+    }
+
+    pub fn target_detach() -> MiCommand {
+        MiCommand { operation: "target-detach".into(), ..Default::default() }
+    }
+
     pub fn list_thread_groups(list_all_available: bool, thread_group_ids: &[u32]) -> MiCommand {
         MiCommand {
-            operation: "list-thread-groups",
+            operation: "list-thread-groups".into(),
             options: if list_all_available {
                 Some(vec![OsString::from("--available")])
             } else {
                 None
             },
-            parameters: Some(thread_group_ids.iter().map(|id| id.to_string().into()).collect()),
+            parameters: if thread_group_ids.is_empty() {
+                None
+            } else {
+                Some(thread_group_ids.iter().map(|id| id.to_string().into()).collect())
+            },
         }
     }
 
@@ -439,7 +1026,7 @@ impl MiCommand {
         frame_addr: Option<u64>, /* none: current frame */
     ) -> MiCommand {
         MiCommand {
-            operation: "var-create",
+            operation: "var-create".into(),
             options: None,
             parameters: Some(vec![
                 name.unwrap_or_else(|| "\"-\"".into()),
@@ -455,7 +1042,7 @@ impl MiCommand {
             parameters.push("-c".into());
         }
         parameters.push(name.into());
-        MiCommand { operation: "var-delete", options: None, parameters: Some(parameters) }
+        MiCommand { operation: "var-delete".into(), options: None, parameters: Some(parameters) }
     }
 
     pub fn var_list_children(
@@ -464,7 +1051,7 @@ impl MiCommand {
         from_to: Option<std::ops::Range<u64>>,
     ) -> MiCommand {
         let mut cmd = MiCommand {
-            operation: "var-list-children",
+            operation: "var-list-children".into(),
             options: None,
             parameters: Some(vec![
                 if print_values { "--all-values" } else { "--no-values" }.into(),
@@ -478,9 +1065,38 @@ impl MiCommand {
         cmd
     }
 
+    /// Update the given variable object and its children, or all variable
+    /// objects if None
+    pub fn var_update(name: Option<&str>, print_values: PrintValue) -> MiCommand {
+        MiCommand {
+            operation: "var-update".into(),
+            options: None,
+            parameters: Some(vec![
+                print_values.to_string().into(),
+                name.map(OsString::from).unwrap_or_else(|| "\"*\"".into()),
+            ]),
+        }
+    }
+
+    pub fn var_assign(name: impl Into<OsString>, expression: &str) -> MiCommand {
+        MiCommand {
+            operation: "var-assign".into(),
+            options: None,
+            parameters: Some(vec![name.into(), escape_command(expression).into()]),
+        }
+    }
+
+    pub fn var_set_format(name: impl Into<OsString>, format: VarFormat) -> MiCommand {
+        MiCommand {
+            operation: "var-set-format".into(),
+            options: None,
+            parameters: Some(vec![name.into(), format.to_string().into()]),
+        }
+    }
+
     pub fn data_list_register_names(reg_list: Option<Vec<usize>>) -> MiCommand {
         MiCommand {
-            operation: "data-list-register-names",
+            operation: "data-list-register-names".into(),
             options: if let Some(list) = reg_list {
                 Some(list.iter().map(|x| x.to_string().into()).collect())
             } else {
@@ -490,13 +1106,13 @@ impl MiCommand {
         }
     }
 
-    /// fmt: "x": hex, "d": decimal, "o": octal, "r": raw, "N": natural
+    /// fmt: "x": hex, "t": binary, "d": decimal, "o": octal, "r": raw, "N": natural
     pub fn data_list_register_values(
         fmt: RegisterFormat,
         reg_list: Option<Vec<usize>>,
     ) -> MiCommand {
         MiCommand {
-            operation: "data-list-register-values",
+            operation: "data-list-register-values".into(),
             options: if let Some(list) = &reg_list {
                 Some(
                     vec![fmt.to_string().into()]
@@ -511,10 +1127,10 @@ impl MiCommand {
         }
     }
 
-    /// List registers that have changed since the last stop.
-    #[allow(dead_code)]
+    /// List registers that have changed since the last time this command was
+    /// run
     pub fn data_list_changed_registers() -> MiCommand {
-        MiCommand { operation: "data-list-changed-registers", ..Default::default() }
+        MiCommand { operation: "data-list-changed-registers".into(), ..Default::default() }
     }
 
     /// Read all accessible memory regions in the specified range
@@ -527,11 +1143,357 @@ impl MiCommand {
             if let Some(offset) = offset { vec![format!("-o {}", offset).into()] } else { vec![] };
         options.push(address.into());
         options.push(count.to_string().into());
-        MiCommand { operation: "data-read-memory-bytes", options: Some(options), parameters: None }
+        MiCommand {
+            operation: "data-read-memory-bytes".into(),
+            options: Some(options),
+            parameters: None,
+        }
+    }
+
+    /// Write the hex encoded contents at the address, the contents are
+    /// repeated to fill count bytes if count is greater than their length
+    pub fn data_write_memory_bytes(
+        address: &str,
+        contents: &str,
+        count: Option<usize>,
+    ) -> MiCommand {
+        let mut parameters: Vec<OsString> = vec![escape_command(address).into(), contents.into()];
+        if let Some(count) = count {
+            parameters.push(count.to_string().into());
+        }
+        MiCommand {
+            operation: "data-write-memory-bytes".into(),
+            options: None,
+            parameters: Some(parameters),
+        }
+    }
+
+    /// Write the values of expressions to registers given by their numbers
+    pub fn data_write_register_values(fmt: RegisterFormat, values: &[(usize, &str)]) -> MiCommand {
+        MiCommand {
+            operation: "data-write-register-values".into(),
+            options: None,
+            parameters: Some(
+                std::iter::once(fmt.to_string().into())
+                    .chain(values.iter().flat_map(|(number, value)| {
+                        [number.to_string().into(), escape_command(value).into()]
+                    }))
+                    .collect(),
+            ),
+        }
     }
 
     /// Empty command, used for testing purposes
     pub fn empty() -> MiCommand {
-        MiCommand { operation: "", ..Default::default() }
+        MiCommand { operation: "".into(), ..Default::default() }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_insert_breakpoint() {
+        let options = BreakPointOptions {
+            condition: Some("i == 3".to_string()),
+            ignore_count: Some(2),
+            temporary: true,
+            thread: Some(1),
+            ..Default::default()
+        };
+        let command =
+            MiCommand::insert_breakpoint(BreakPointLocation::Function(None, "main"), &options);
+        assert_eq!(command.operation, "break-insert");
+        assert_eq!(
+            command.options,
+            Some(
+                ["-t", "-c", "\"i == 3\"", "-i", "2", "-p", "1", "main"]
+                    .into_iter()
+                    .map(OsString::from)
+                    .collect()
+            )
+        );
+
+        let command = MiCommand::insert_breakpoint(
+            BreakPointLocation::Address(0x1234),
+            &BreakPointOptions::default(),
+        );
+        assert_eq!(command.options, Some(vec![OsString::from("*0x1234")]));
+    }
+
+    #[test]
+    fn test_breakpoint_commands() {
+        let number: BreakPointNumber = "2.1".parse().unwrap();
+        assert_eq!(number, BreakPointNumber { major: 2, minor: Some(1) });
+
+        let command = MiCommand::breakpoint_condition(number, Some("x > 1"));
+        assert_eq!(
+            command.parameters,
+            Some(vec![OsString::from("2.1"), OsString::from("\"x > 1\"")])
+        );
+        let command = MiCommand::breakpoint_condition(number, None);
+        assert_eq!(command.parameters, Some(vec![OsString::from("2.1")]));
+
+        let command = MiCommand::breakpoint_commands(
+            "3".parse().unwrap(),
+            &["print x".to_string(), "continue".to_string()],
+        );
+        assert_eq!(command.operation, "break-commands");
+        assert_eq!(
+            command.parameters,
+            Some(["3", "\"print x\"", "\"continue\""].into_iter().map(OsString::from).collect())
+        );
+    }
+
+    #[test]
+    fn test_var_commands() {
+        let command = MiCommand::var_update(None, PrintValue::AllValues);
+        assert_eq!(
+            command.parameters,
+            Some(vec![OsString::from("--all-values"), OsString::from("\"*\"")])
+        );
+
+        let command = MiCommand::var_assign("var1.x", "y + 1");
+        assert_eq!(
+            command.parameters,
+            Some(vec![OsString::from("var1.x"), OsString::from("\"y + 1\"")])
+        );
+
+        let format: VarFormat = "zero-hexadecimal".parse().unwrap();
+        let command = MiCommand::var_set_format("var1", format);
+        assert_eq!(
+            command.parameters,
+            Some(vec![OsString::from("var1"), OsString::from("zero-hexadecimal")])
+        );
+    }
+
+    #[test]
+    fn test_output_expression() {
+        let command = MiCommand::output_expression("buf[i]", VarFormat::ZeroHexadecimal)
+            .thread_frame(Some(2), None);
+        assert_eq!(
+            command.options,
+            Some(
+                ["--thread", "2", "console", "\"output/z buf[i]\""]
+                    .into_iter()
+                    .map(OsString::from)
+                    .collect()
+            )
+        );
+        let command = MiCommand::output_expression("p", VarFormat::Natural);
+        assert_eq!(
+            command.options,
+            Some(["console", "\"output p\""].into_iter().map(OsString::from).collect())
+        );
+    }
+
+    #[test]
+    fn test_thread_frame() {
+        let command =
+            MiCommand::data_evaluate_expression("s == \"a\"").thread_frame(Some(2), Some(1));
+        assert_eq!(
+            command.parameters,
+            Some(
+                ["--thread", "2", "--frame", "1", "\"s == \\\"a\\\"\""]
+                    .into_iter()
+                    .map(OsString::from)
+                    .collect()
+            )
+        );
+
+        let command =
+            MiCommand::raw("stack-list-frames", Some(vec!["--no-frame-filters".into()]), None)
+                .thread_frame(Some(3), None);
+        assert_eq!(
+            command.options,
+            Some(["--thread", "3", "--no-frame-filters"].into_iter().map(OsString::from).collect())
+        );
+        assert_eq!("x".parse::<VarFormat>(), Ok(VarFormat::Hexadecimal));
+    }
+
+    #[test]
+    fn test_data_disassemble() {
+        let command = MiCommand::data_disassemble_address(
+            "$pc",
+            "$pc + 64",
+            DisassembleMode::DisassemblyWithRawOpcodes,
+        );
+        assert_eq!(
+            command.options,
+            Some(["-s", "\"$pc\"", "-e", "\"$pc + 64\""].into_iter().map(OsString::from).collect())
+        );
+        assert_eq!(command.parameters, Some(vec![OsString::from("2")]));
+
+        let command = MiCommand::data_disassemble_function(
+            "main",
+            DisassembleMode::MixedSourceAndDisassemblyWithRawOpcodes,
+        );
+        assert_eq!(command.options, Some(vec![OsString::from("-a"), OsString::from("\"main\"")]));
+        assert_eq!(command.parameters, Some(vec![OsString::from("3")]));
+    }
+
+    #[test]
+    fn test_stack_commands() {
+        let command = MiCommand::stack_info_frame(Some((1, 2)));
+        assert_eq!(command.operation, "stack-info-frame");
+        assert_eq!(
+            command.parameters,
+            Some(["--thread", "1", "--frame", "2"].into_iter().map(OsString::from).collect())
+        );
+
+        let command = MiCommand::stack_list_arguments(PrintValue::SimpleValues, Some(5), None);
+        assert_eq!(
+            command.parameters,
+            Some(["--simple-values", "5", "99999"].into_iter().map(OsString::from).collect())
+        );
+    }
+
+    #[test]
+    fn test_target_commands() {
+        let command = MiCommand::target_select("localhost:1234", true);
+        assert_eq!(command.operation, "target-select");
+        assert_eq!(
+            command.parameters,
+            Some(vec![OsString::from("extended-remote"), OsString::from("\"localhost:1234\"")])
+        );
+
+        let command = MiCommand::target_select_core(Path::new("/tmp/core.1234"));
+        assert_eq!(
+            command.parameters,
+            Some(vec![OsString::from("core"), OsString::from("\"/tmp/core.1234\"")])
+        );
+
+        let command = MiCommand::target_file_get("/tmp/core", Path::new("my core"));
+        assert_eq!(
+            command.parameters,
+            Some(vec![OsString::from("\"/tmp/core\""), OsString::from("\"my core\"")])
+        );
+    }
+
+    #[test]
+    fn test_data_register_values() {
+        let format: RegisterFormat = "binary".parse().unwrap();
+        let command = MiCommand::data_list_register_values(format, Some(vec![0, 16]));
+        assert_eq!(
+            command.options,
+            Some(["t", "0", "16"].into_iter().map(OsString::from).collect())
+        );
+        assert_eq!("N".parse::<RegisterFormat>(), Ok(RegisterFormat::Natural));
+        assert!("b".parse::<RegisterFormat>().is_err());
+    }
+
+    #[test]
+    fn test_data_write() {
+        let command = MiCommand::data_write_memory_bytes("&buf[2]", "90ff", Some(8));
+        assert_eq!(command.operation, "data-write-memory-bytes");
+        assert_eq!(
+            command.parameters,
+            Some(["\"&buf[2]\"", "90ff", "8"].into_iter().map(OsString::from).collect())
+        );
+
+        let command = MiCommand::data_write_register_values(RegisterFormat::Hex, &[(0, "0x2a")]);
+        assert_eq!(
+            command.parameters,
+            Some(["x", "0", "\"0x2a\""].into_iter().map(OsString::from).collect())
+        );
+    }
+
+    #[test]
+    fn test_signal_commands() {
+        let command = MiCommand::handle_signal("SIGUSR1", &["nostop", "noprint", "pass"]);
+        assert_eq!(
+            command.options,
+            Some(vec![
+                OsString::from("console"),
+                OsString::from("\"handle SIGUSR1 nostop noprint pass\"")
+            ])
+        );
+        let command = MiCommand::handle_signal("SIGSEGV", &[]);
+        assert_eq!(
+            command.options,
+            Some(vec![OsString::from("console"), OsString::from("\"info signals SIGSEGV\"")])
+        );
+    }
+
+    #[test]
+    fn test_catch() {
+        let command = MiCommand::catch(CatchEvent::Throw, Some("std::out_of_range"), true);
+        assert_eq!(command.operation, "catch-throw");
+        assert_eq!(
+            command.options,
+            Some(["-t", "-r", "\"std::out_of_range\""].into_iter().map(OsString::from).collect())
+        );
+
+        let command = MiCommand::catch("load".parse().unwrap(), None, false);
+        assert_eq!(command.operation, "catch-load");
+        assert_eq!(command.parameters, Some(vec![OsString::from("\".*\"")]));
+
+        let command = MiCommand::catch(CatchEvent::Syscall, Some("write openat"), false);
+        assert_eq!(
+            command.options,
+            Some(vec![OsString::from("console"), OsString::from("\"catch syscall write openat\"")])
+        );
+        let command = MiCommand::catch(CatchEvent::Fork, None, true);
+        assert_eq!(
+            command.options,
+            Some(vec![OsString::from("console"), OsString::from("\"tcatch fork\"")])
+        );
+        assert!("throws".parse::<CatchEvent>().is_err());
+    }
+
+    #[test]
+    fn test_exec_commands() {
+        let command = MiCommand::exec_step().repeat(Some(3));
+        assert_eq!(command.parameters, Some(vec![OsString::from("3")]));
+        assert_eq!(MiCommand::exec_next_instruction().repeat(None).parameters, None);
+
+        let command = MiCommand::exec_until(Some("main.c:42"));
+        assert_eq!(command.operation, "exec-until");
+        assert_eq!(command.parameters, Some(vec![OsString::from("\"main.c:42\"")]));
+        assert_eq!(MiCommand::exec_until(None).parameters, None);
+
+        let command = MiCommand::exec_return(Some("-1"));
+        assert_eq!(command.parameters, Some(vec![OsString::from("\"-1\"")]));
+
+        let command = MiCommand::advance("*0x401000");
+        assert_eq!(
+            command.options,
+            Some(vec![OsString::from("console"), OsString::from("\"advance *0x401000\"")])
+        );
+    }
+
+    #[test]
+    fn test_reverse_commands() {
+        let command = MiCommand::exec_finish().reverse().thread_frame(Some(2), None);
+        assert_eq!(command.operation, "exec-finish");
+        assert_eq!(
+            command.options,
+            Some(["--thread", "2", "--reverse"].into_iter().map(OsString::from).collect())
+        );
+        assert!(command.is_reverse());
+        assert!(!MiCommand::exec_finish().is_reverse());
+
+        let command = MiCommand::record_start("pt".parse().unwrap());
+        assert_eq!(command.operation, "interpreter-exec");
+        assert_eq!(
+            command.options,
+            Some(vec![OsString::from("console"), OsString::from("\"record btrace pt\"")])
+        );
+        assert!("replay".parse::<RecordMethod>().is_err());
+    }
+
+    #[test]
+    fn test_insert_watchpoint() {
+        let command = MiCommand::insert_watchpoint("buf[i] + 1", "access".parse().unwrap());
+        assert_eq!(command.operation, "break-watch");
+        assert_eq!(command.options, Some(vec![OsString::from("-a")]));
+        assert_eq!(command.parameters, Some(vec![OsString::from("\"buf[i] + 1\"")]));
+        assert!("modify".parse::<WatchMode>().is_err());
+    }
+
+    #[test]
+    fn test_escape_command() {
+        assert_eq!(escape_command("print \"a\\b\"\n"), "\"print \\\"a\\\\b\\\"\\n\"");
     }
 }
@@ -13,7 +13,7 @@ use tokio::io::BufReader;
 use tokio::process::{Child, Command};
 use tokio::sync::Mutex;
 use tokio::sync::mpsc::{self, Sender};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::error::{AppError, AppResult};
 
@@ -212,7 +212,8 @@ impl GDB {
         &mut self,
         command: C,
     ) -> AppResult<output::ResultRecord> {
-        if self.is_running() {
+        // The program can only be interrupted while it is running
+        if self.is_running() && command.borrow().operation != "exec-interrupt" {
             return Err(AppError::GDBBusy);
         }
 
@@ -233,25 +234,32 @@ impl GDB {
             .await
             .expect("write interpreter command");
 
-        match self.result_output.recv().await {
-            Some(record) => match record.token {
-                Some(token) => {
-                    if token == command_token {
-                        Ok(record)
-                    } else {
-                        Err(AppError::InvalidArgument(format!(
+        loop {
+            match self.result_output.recv().await {
+                Some(record) => match record.token {
+                    Some(token) if token == command_token => return Ok(record),
+                    // The result of a command which timed out, e.g. an
+                    // -exec-interrupt GDB only read after being interrupted
+                    Some(token) if token < command_token => {
+                        warn!("Dropping the result of a previous command {}", token);
+                        continue;
+                    }
+                    Some(token) => {
+                        return Err(AppError::InvalidArgument(format!(
                             "Unexpected command token: {}",
                             token
-                        )))
+                        )));
+                    }
+                    None if command.borrow().operation.is_empty() => return Ok(record),
+                    None => {
+                        return Err(AppError::GDBError(format!(
+                            "No command token, expecting {}",
+                            command_token
+                        )));
                     }
-                }
-                None if command.borrow().operation.is_empty() => Ok(record),
-                None => Err(AppError::GDBError(format!(
-                    "No command token, expecting {}",
-                    command_token
-                ))),
-            },
-            None => Err(AppError::GDBError("no result, expecting {}".to_string())),
+                },
+                None => return Err(AppError::GDBError("no result, expecting {}".to_string())),
+            }
         }
     }
 
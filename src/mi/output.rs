@@ -1,21 +1,24 @@
 // use std::io::{BufRead, BufReader, Read};
+use std::fmt;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use anyhow::Result;
 use nom::branch::alt;
 use nom::bytes::complete::{is_not, tag, take_while_m_n};
-use nom::character::complete::{char, digit1, line_ending, multispace1};
+use nom::character::complete::{char, digit1, line_ending, multispace1, not_line_ending};
 use nom::combinator::{map, map_opt, map_res, opt, value, verify};
 use nom::error::{FromExternalError, ParseError};
 use nom::multi::{fold, many0, separated_list0};
 use nom::sequence::{delimited, preceded, separated_pair};
 use nom::{IResult, Parser};
+use serde::Serialize;
 use serde_json::{Map, Value};
 use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 use tracing::{debug, error, info};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ResultClass {
     Done,
     Running,
@@ -31,6 +34,12 @@ pub enum BreakPointEvent {
     Modified,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordEvent {
+    Started,
+    Stopped,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ThreadEvent {
     Created,
@@ -48,6 +57,7 @@ pub enum AsyncClass {
     LibraryLoaded,
     Thread(ThreadEvent),
     BreakPoint(BreakPointEvent),
+    Record(RecordEvent),
     Other(String), //?
 }
 
@@ -58,7 +68,64 @@ pub enum AsyncKind {
     Notify,
 }
 
-#[derive(Debug, Clone)]
+impl fmt::Display for BreakPointEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BreakPointEvent::Created => write!(f, "breakpoint-created"),
+            BreakPointEvent::Deleted => write!(f, "breakpoint-deleted"),
+            BreakPointEvent::Modified => write!(f, "breakpoint-modified"),
+        }
+    }
+}
+
+impl fmt::Display for RecordEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecordEvent::Started => write!(f, "record-started"),
+            RecordEvent::Stopped => write!(f, "record-stopped"),
+        }
+    }
+}
+
+impl fmt::Display for ThreadEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThreadEvent::Created => write!(f, "thread-created"),
+            ThreadEvent::GroupStarted => write!(f, "thread-group-started"),
+            ThreadEvent::Exited => write!(f, "thread-exited"),
+            ThreadEvent::GroupExited => write!(f, "thread-group-exited"),
+            ThreadEvent::Selected => write!(f, "thread-selected"),
+        }
+    }
+}
+
+impl fmt::Display for AsyncClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AsyncClass::Running => write!(f, "running"),
+            AsyncClass::Stopped => write!(f, "stopped"),
+            AsyncClass::CmdParamChanged => write!(f, "cmd-param-changed"),
+            AsyncClass::LibraryLoaded => write!(f, "library-loaded"),
+            AsyncClass::Thread(event) => write!(f, "{}", event),
+            AsyncClass::BreakPoint(event) => write!(f, "{}", event),
+            AsyncClass::Record(event) => write!(f, "{}", event),
+            AsyncClass::Other(class) => write!(f, "{}", class),
+        }
+    }
+}
+
+impl fmt::Display for AsyncKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AsyncKind::Exec => write!(f, "exec"),
+            AsyncKind::Status => write!(f, "status"),
+            AsyncKind::Notify => write!(f, "notify"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum StreamKind {
     Console,
     Target,
@@ -70,6 +137,8 @@ pub struct ResultRecord {
     pub(crate) token: Option<u64>,
     pub class: ResultClass,
     pub results: Value,
+    /// Console stream output received before the result record
+    pub console: String,
 }
 
 #[derive(Debug, Clone)]
@@ -97,6 +166,8 @@ pub async fn process_output<T: AsyncRead + Unpin>(
     is_running: Arc<AtomicBool>,
 ) {
     let mut reader = BufReader::new(output);
+    // Console output of the pending command, attached to its result record
+    let mut console = String::new();
 
     loop {
         let mut buffer = String::new();
@@ -116,7 +187,8 @@ pub async fn process_output<T: AsyncRead + Unpin>(
                 };
                 debug!("{:?}", &parse_result);
                 match parse_result {
-                    Output::Result(record) => {
+                    Output::Result(mut record) => {
+                        record.console = std::mem::take(&mut console);
                         match record.class {
                             ResultClass::Running => is_running.store(true, Ordering::SeqCst),
                             //Apparently sometimes gdb first claims to be running, only to then
@@ -127,10 +199,16 @@ pub async fn process_output<T: AsyncRead + Unpin>(
                         result_pipe.send(record).await.expect("send result to pipe");
                     }
                     Output::OutOfBand(record) => {
-                        if let OutOfBandRecord::AsyncRecord { class: AsyncClass::Stopped, .. } =
-                            record
-                        {
-                            is_running.store(false, Ordering::SeqCst);
+                        match &record {
+                            OutOfBandRecord::AsyncRecord { class: AsyncClass::Stopped, .. } => {
+                                is_running.store(false, Ordering::SeqCst);
+                                // Output of a running program doesn't belong to the next command
+                                console.clear();
+                            }
+                            OutOfBandRecord::StreamRecord { kind: StreamKind::Console, data } => {
+                                console.push_str(data);
+                            }
+                            _ => {}
                         }
                         out_of_band_pipe
                             .send(record)
@@ -143,7 +221,7 @@ pub async fn process_output<T: AsyncRead + Unpin>(
                         out_of_band_pipe
                             .send(OutOfBandRecord::StreamRecord {
                                 kind: StreamKind::Target,
-                                data: text,
+                                data: text + "\n",
                             })
                             .await
                             .expect("send out of band record to pipe");
@@ -329,6 +407,7 @@ fn result_record(input: &str) -> IResult<&str, Output> {
                 token: t,
                 class: c,
                 results: Value::Object(to_map(results)),
+                console: String::new(),
             })
         },
     )
@@ -358,6 +437,8 @@ fn async_class(input: &str) -> IResult<&str, AsyncClass> {
         value(AsyncClass::BreakPoint(BreakPointEvent::Created), tag("breakpoint-created")),
         value(AsyncClass::BreakPoint(BreakPointEvent::Deleted), tag("breakpoint-deleted")),
         value(AsyncClass::BreakPoint(BreakPointEvent::Modified), tag("breakpoint-modified")),
+        value(AsyncClass::Record(RecordEvent::Started), tag("record-started")),
+        value(AsyncClass::Record(RecordEvent::Stopped), tag("record-stopped")),
         map(is_not(","), |msg: &str| AsyncClass::Other(msg.to_string())),
     ))
     .parse(input)
@@ -368,7 +449,8 @@ fn async_class(input: &str) -> IResult<&str, AsyncClass> {
 /// and async-class is one of: running, stopped, thread-created,
 /// thread-group-started, thread-exited, thread-group-exited, thread-selected,
 /// cmd-param-changed, library-loaded, breakpoint-created, breakpoint-deleted,
-/// breakpoint-modified, other and result is a json object
+/// breakpoint-modified, record-started, record-stopped, other and result is a
+/// json object
 fn async_record(input: &str) -> IResult<&str, OutOfBandRecord> {
     map(
         (opt(token), async_kind, async_class, many0(preceded(char(','), key_value))),
@@ -407,8 +489,9 @@ fn prompt(input: &str) -> IResult<&str, Output> {
     value(Output::GDBLine, tag("(gdb) ")).parse(input)
 }
 
+/// anything else, e.g. the output of the program being debugged
 fn debug_line(input: &str) -> IResult<&str, Output> {
-    Ok(("", Output::SomethingElse(input.to_string())))
+    map(not_line_ending, |line: &str| Output::SomethingElse(line.to_string())).parse(input)
 }
 
 fn output(input: &str) -> IResult<&str, Output> {
@@ -445,6 +528,35 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_async_class_name() {
+        for line in [
+            "=thread-group-exited,id=\"i1\",exit-code=\"0\"\n",
+            "=breakpoint-modified,bkpt={number=\"1\"}\n",
+            "=library-unloaded,id=\"/lib/libc.so.6\"\n",
+            "=record-started,thread-group=\"i1\",method=\"btrace\",format=\"pt\"\n",
+            "*stopped,reason=\"exited-normally\"\n",
+        ] {
+            let output = Output::parse(line).expect("parse output");
+            if let Output::OutOfBand(OutOfBandRecord::AsyncRecord { kind, class, .. }) = output {
+                let name = line[1..].split(',').next().unwrap();
+                assert_eq!(class.to_string(), name);
+                assert_eq!(kind.to_string(), if line.starts_with('*') { "exec" } else { "notify" });
+            } else {
+                panic!("output is not an async record");
+            }
+        }
+    }
+
+    #[test]
+    fn test_program_output() {
+        match Output::parse("Hello, world!\n") {
+            Ok(Output::SomethingElse(text)) => assert_eq!(text, "Hello, world!"),
+            Ok(output) => panic!("unexpected output: {:?}", output),
+            Err(e) => panic!("parse output failed: {}", e),
+        }
+    }
+
     #[test]
     fn test_result_record() {
         let output = match Output::parse(
@@ -12,14 +12,17 @@ use nom::character::complete::{alphanumeric1, space0};
 use nom::combinator::map;
 use nom::sequence::{delimited, preceded, separated_pair};
 use nom::{IResult, Parser};
-use serde::{Deserialize, Serialize, de};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_with::{DisplayFromStr, serde_as, skip_serializing_none};
 use tracing::debug;
 
 use crate::error::AppError;
 use crate::mi::commands::BreakPointNumber;
+use crate::mi::output::{ResultClass, StreamKind};
 
 /// GDB session information
+#[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GDBSession {
     /// Session ID
@@ -28,6 +31,31 @@ pub struct GDBSession {
     pub status: GDBSessionStatus,
     /// Creation time
     pub created_at: u64,
+    /// Exit code of the program, in octal, if it has exited
+    pub exit_code: Option<String>,
+    /// Reason of the last stop
+    pub stop_reason: Option<StopReason>,
+    /// The frame where the program last stopped
+    pub frame: Option<StackFrame>,
+    /// The ID of the selected thread
+    pub current_thread: Option<String>,
+    /// The ID of the process GDB is attached to
+    pub attached_pid: Option<u32>,
+    /// The core dump loaded in the session
+    pub core_file: Option<PathBuf>,
+    /// Method of the active process record, "full" or "btrace"
+    pub record_method: Option<String>,
+}
+
+/// Remote target to connect a session to
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct RemoteTarget {
+    /// The target, host:port or a serial device, e.g. 'localhost:1234' or '/dev/ttyUSB0'
+    pub address: String,
+    /// Whether to connect in extended remote mode, where programs can be run and attached to,
+    /// defaults to false
+    #[serde(default)]
+    pub extended: bool,
 }
 
 /// GDB session status
@@ -39,10 +67,157 @@ pub enum GDBSessionStatus {
     Running,
     /// Program stopped at breakpoint
     Stopped,
-    /// Session terminated
+    /// Program stopped in the execution log of a process record, execution
+    /// commands replay the log until its end instead of running the program
+    Replaying,
+    /// Program exited, the session can still run it again
+    Exited,
+    /// Detached from the process, the session can attach again or run the
+    /// program
+    Detached,
+    /// GDB process exited, the session is no longer usable
     Terminated,
 }
 
+/// How GDB handles a signal, as listed by `handle` or `info signals`
+#[derive(Debug, Clone, Serialize)]
+pub struct SignalHandling {
+    pub signal: String,
+    /// Whether the program stops when it receives the signal
+    pub stop: bool,
+    /// Whether GDB prints a message when the program receives the signal
+    pub print: bool,
+    /// Whether the signal is passed to the program
+    pub pass: bool,
+    pub description: String,
+}
+
+/// Parse the signal table printed by `handle` or `info signals`
+pub fn parse_signal_handling(console: &str) -> Vec<SignalHandling> {
+    let flag = |s: &str| match s {
+        "Yes" => Some(true),
+        "No" => Some(false),
+        _ => None,
+    };
+    console
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let signal = parts.next()?;
+            let stop = flag(parts.next()?)?;
+            let print = flag(parts.next()?)?;
+            let pass = flag(parts.next()?)?;
+            Some(SignalHandling {
+                signal: signal.to_string(),
+                stop,
+                print,
+                pass,
+                description: parts.collect::<Vec<_>>().join(" "),
+            })
+        })
+        .collect()
+}
+
+/// Status of the process record, as described by `info record`
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordStatus {
+    /// Active record target, e.g. "record-full" or "record-btrace", absent if
+    /// the program is not recorded
+    pub target: Option<String>,
+    /// Whether the program is stopped in the execution log
+    pub replaying: bool,
+    /// Details of the record, e.g. the number of instructions recorded
+    pub details: String,
+}
+
+/// Parse the output of `info record`
+pub fn parse_record_status(console: &str) -> RecordStatus {
+    let target = console
+        .lines()
+        .find_map(|l| l.strip_prefix("Active record target: "))
+        .map(|t| t.trim().to_string());
+    // "Replay mode:" for record full, "Replay in progress." for record btrace
+    let replaying = target.is_some()
+        && console
+            .lines()
+            .any(|l| l.starts_with("Replay mode:") || l.starts_with("Replay in progress"));
+    RecordStatus { target, replaying, details: console.trim().to_string() }
+}
+
+/// A register of the architecture with the groups it belongs to
+#[derive(Debug, Clone, Serialize)]
+pub struct RegisterInfo {
+    pub number: usize,
+    pub name: String,
+    /// Register groups, e.g. "general", "float", "vector", "system", "all"
+    pub groups: Vec<String>,
+}
+
+/// Parse the table printed by `maint print register-groups` into the groups of
+/// each register number. Registers without a name are skipped
+pub fn parse_register_groups(console: &str) -> HashMap<usize, Vec<String>> {
+    // Name, Nr, Rel, Offset, Size, Type and Groups, the type or the groups may
+    // be empty so the groups are found by the offset of their header
+    let mut lines = console.lines();
+    let Some(offset) = lines.by_ref().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        (parts.next() == Some("Name") && parts.next() == Some("Nr"))
+            .then(|| line.trim_end().rfind(' ').map(|i| i + 1))
+            .flatten()
+    }) else {
+        return HashMap::new();
+    };
+
+    lines
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?;
+            let number = parts.next()?.parse::<usize>().ok()?;
+            if name == "''" {
+                return None;
+            }
+            let (head, mut groups) = line.split_at_checked(offset).unwrap_or((line, ""));
+            // A long type runs into the column and pushes the groups right
+            if !head.ends_with(' ') {
+                groups = groups.split_once(' ').map_or("", |(_, groups)| groups);
+            }
+            let groups =
+                groups.trim().split(',').filter(|g| !g.is_empty()).map(|g| g.to_string()).collect();
+            Some((number, groups))
+        })
+        .collect()
+}
+
+/// Parse the register names printed by `info registers`, vector registers
+/// may continue on the next lines
+pub fn parse_info_registers(console: &str) -> Vec<String> {
+    console
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Parse the architecture from the output of `show architecture`, e.g.
+/// `The target architecture is set to "auto" (currently "i386:x86-64").`
+pub fn parse_architecture(console: &str) -> Option<String> {
+    console.rsplit('"').nth(1).filter(|arch| !arch.is_empty()).map(|arch| arch.to_string())
+}
+
+/// Result of a raw GDB/MI command
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct MiResult {
+    /// Result class, e.g. done, running, connected, error or exit
+    pub class: ResultClass,
+    /// Results of the command
+    pub results: serde_json::Value,
+    /// Console output of the command
+    pub console: Option<String>,
+}
+
 /// GDB command request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GDBCommandRequest {
@@ -140,8 +315,10 @@ impl<'de> Deserialize<'de> for Enabled {
     }
 }
 
+#[serde_as]
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct BreakPoint {
     pub number: BreakPointNumber,
     #[serde(rename = "addr")]
@@ -152,6 +329,57 @@ pub struct BreakPoint {
     pub r#type: String,
     #[serde(rename = "disp")]
     pub display: String,
+    /// The watched expression for watchpoints
+    pub what: Option<String>,
+    /// Condition of the breakpoint
+    pub cond: Option<String>,
+    /// Number of times the breakpoint has been hit
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub times: Option<usize>,
+    /// Remaining number of hits to ignore
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub ignore: Option<usize>,
+    /// The thread the breakpoint is restricted to
+    pub thread: Option<String>,
+    /// The location as originally given by the user
+    pub original_location: Option<String>,
+    /// Locations of a breakpoint with multiple locations, e.g. in a template
+    /// or an inlined function
+    pub locations: Option<Vec<ChildBreakPoint>>,
+}
+
+/// One of the locations of a breakpoint with multiple locations
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChildBreakPoint {
+    pub number: BreakPointNumber,
+    pub enabled: Enabled,
+    #[serde(rename = "addr")]
+    pub address: Option<Address64>,
+    #[serde(rename = "func")]
+    pub function: Option<String>,
+    #[serde(flatten)]
+    pub src_pos: Option<SrcPosition>,
+}
+
+/// Watchpoint as reported by -break-watch and in the stop event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchPoint {
+    pub number: BreakPointNumber,
+    #[serde(rename = "exp")]
+    pub expression: String,
+}
+
+/// Value of the watched expression when a watchpoint triggers
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchValue {
+    /// Value before the write
+    pub old: Option<String>,
+    /// Value after the write
+    pub new: Option<String>,
+    /// Value read, for read watchpoints and access watchpoints not changing it
+    pub value: Option<String>,
 }
 
 pub struct BreakPointSet {
@@ -198,8 +426,10 @@ impl std::ops::Deref for BreakPointSet {
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StackFrame {
-    /// Frame level
+    /// Frame level, not present in stop events where it is always the topest
+    /// frame
     #[serde_as(as = "DisplayFromStr")]
+    #[serde(default)]
     pub level: u32,
     /// Function name
     #[serde(rename = "func")]
@@ -216,6 +446,207 @@ pub struct StackFrame {
     pub address: Option<Address64>,
     /// Arch
     pub arch: Option<String>,
+    /// Arguments of the function
+    pub args: Option<Vec<Variable>>,
+    /// Local variables of the function, only listed in core reports
+    pub locals: Option<Vec<Variable>>,
+}
+
+/// Arguments of a frame, as listed by -stack-list-arguments
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameArguments {
+    #[serde_as(as = "DisplayFromStr")]
+    pub level: u32,
+    pub args: Vec<Variable>,
+}
+
+/// A page of the stack frames
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StackFrames {
+    /// Number of frames of the stack
+    pub depth: usize,
+    pub frames: Vec<StackFrame>,
+}
+
+/// Process available to attach to
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessInfo {
+    /// Process ID
+    pub id: String,
+    /// Command line of the process
+    pub description: Option<String>,
+    /// Owner of the process
+    pub user: Option<String>,
+    /// Cores the process is running on
+    pub cores: Option<Vec<String>>,
+}
+
+/// Thread information
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ThreadInfo {
+    /// Thread ID, used to refer to the thread in the tools
+    pub id: String,
+    /// Target specific thread ID, e.g. "Thread 0x7ffff7d89740 (LWP 12345)"
+    pub target_id: String,
+    /// Thread name, if known
+    pub name: Option<String>,
+    /// Additional target specific information
+    pub details: Option<String>,
+    /// "stopped" or "running"
+    pub state: String,
+    /// The processor core on which the thread is running
+    pub core: Option<String>,
+    /// The current frame of a stopped thread
+    pub frame: Option<StackFrame>,
+}
+
+/// Threads of the program
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ThreadList {
+    pub threads: Vec<ThreadInfo>,
+    /// The ID of the selected thread, absent if there are no threads
+    pub current_thread_id: Option<String>,
+}
+
+/// Reason of a stop reported in the `*stopped` async record
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StopReason {
+    /// A breakpoint was reached
+    BreakpointHit,
+    /// A watchpoint was triggered
+    WatchpointTrigger,
+    /// A read watchpoint was triggered
+    ReadWatchpointTrigger,
+    /// An access watchpoint was triggered
+    AccessWatchpointTrigger,
+    /// An -exec-finish or similar CLI command was accomplished
+    FunctionFinished,
+    /// An -exec-until or similar CLI command was accomplished
+    LocationReached,
+    /// A watchpoint has gone out of scope
+    WatchpointScope,
+    /// An -exec-next, -exec-next-instruction, -exec-step, -exec-step-instruction
+    /// or similar CLI command was accomplished
+    EndSteppingRange,
+    /// The inferior exited because of a signal
+    ExitedSignalled,
+    /// The inferior exited
+    Exited,
+    /// The inferior exited normally
+    ExitedNormally,
+    /// A signal was received by the inferior
+    SignalReceived,
+    /// The inferior has stopped due to a library being loaded or unloaded
+    SolibEvent,
+    /// The inferior has forked
+    Fork,
+    /// The inferior has vforked
+    Vfork,
+    /// The inferior entered a system call
+    SyscallEntry,
+    /// The inferior returned from a system call
+    SyscallReturn,
+    /// The inferior called exec
+    Exec,
+    /// There isn't enough history recorded to continue reverse execution
+    NoHistory,
+    /// Any reason not known yet
+    #[serde(other)]
+    Unknown,
+}
+
+/// Stop event parsed from the `*stopped` async record
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StopEvent {
+    /// Stop reason, may be missing, e.g. after attaching to a process
+    pub reason: Option<StopReason>,
+    /// Number of the breakpoint hit
+    #[serde(rename = "bkptno")]
+    pub breakpoint: Option<String>,
+    /// Watchpoint triggered
+    #[serde(rename = "wpt", alias = "hw-rwpt", alias = "hw-awpt")]
+    pub watchpoint: Option<WatchPoint>,
+    /// Value of the watched expression
+    pub value: Option<WatchValue>,
+    /// Value returned by the function, after finish
+    pub return_value: Option<String>,
+    /// Convenience variable holding the returned value, e.g. "$1"
+    pub gdb_result_var: Option<String>,
+    /// Name of the system call, at a syscall catchpoint
+    pub syscall_name: Option<String>,
+    /// Number of the system call, at a syscall catchpoint
+    pub syscall_number: Option<String>,
+    /// Process ID of the child, at a fork or vfork catchpoint
+    #[serde(rename = "newpid")]
+    pub new_pid: Option<String>,
+    /// Program executed, at an exec catchpoint
+    pub new_exec: Option<String>,
+    /// Libraries loaded, at a load catchpoint
+    #[serde(rename = "added")]
+    pub libraries_loaded: Option<serde_json::Value>,
+    /// Libraries unloaded, at an unload catchpoint
+    #[serde(rename = "removed")]
+    pub libraries_unloaded: Option<serde_json::Value>,
+    /// Number of the watchpoint gone out of scope
+    #[serde(rename = "wpnum")]
+    pub watchpoint_number: Option<String>,
+    /// Name of the signal received
+    pub signal_name: Option<String>,
+    /// Description of the signal received
+    pub signal_meaning: Option<String>,
+    /// Exit code of the inferior, in octal
+    pub exit_code: Option<String>,
+    /// The frame where the program stopped
+    pub frame: Option<StackFrame>,
+    /// The thread that caused the stop
+    pub thread_id: Option<String>,
+    /// Threads stopped, either "all" or a list of thread IDs
+    pub stopped_threads: Option<serde_json::Value>,
+    /// The processor core on which the stop event has happened
+    pub core: Option<String>,
+    /// Console and program output produced while executing the command
+    #[serde(skip_deserializing)]
+    pub output: Option<String>,
+}
+
+impl StopEvent {
+    /// Signal catchpoints report neither a reason nor the breakpoint number,
+    /// decode them from the console output, e.g. "Catchpoint 2 (signal
+    /// SIGUSR1), "
+    pub fn decode_signal_catchpoint(&mut self) {
+        if self.reason.is_some() {
+            return;
+        }
+        let Some((number, description)) = self.output.as_deref().and_then(parse_catchpoint) else {
+            return;
+        };
+        if let Some(signal) = description.strip_prefix("signal ") {
+            self.reason = Some(StopReason::SignalReceived);
+            self.breakpoint = Some(number);
+            self.signal_name = Some(signal.to_string());
+        }
+    }
+}
+
+/// Parse the number and the description of a catchpoint printed to the
+/// console, e.g. "Catchpoint 1 (syscall 'write' [1])"
+pub fn parse_catchpoint(console: &str) -> Option<(String, String)> {
+    console.lines().find_map(|line| {
+        let line = line.split("atchpoint ").nth(1)?;
+        let (number, description) = line.split_once(" (")?;
+        let (description, _) = description.split_once(')')?;
+        number.parse::<BreakPointNumber>().ok()?;
+        Some((number.to_string(), description.to_string()))
+    })
 }
 
 pub enum PrintValue {
@@ -243,7 +674,11 @@ impl FromStr for PrintValue {
 
 impl Display for PrintValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self)
+        match self {
+            PrintValue::NoValues => write!(f, "--no-values"),
+            PrintValue::AllValues => write!(f, "--all-values"),
+            PrintValue::SimpleValues => write!(f, "--simple-values"),
+        }
     }
 }
 
@@ -259,12 +694,89 @@ pub struct Variable {
     pub value: Option<String>,
 }
 
+/// Variable object, created by -var-create or listed as a child
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct VarObject {
+    /// Name of the variable object, used to refer to it in the var tools
+    pub name: String,
+    /// Expression of a child, relative to its parent, e.g. a field name
+    #[serde(rename = "exp")]
+    pub expression: Option<String>,
+    /// Number of children, may be inaccurate for dynamic variable objects
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(default)]
+    pub numchild: usize,
+    /// Value, not present if values were not requested
+    pub value: Option<String>,
+    /// Type
+    pub r#type: Option<String>,
+    /// The thread the variable object is bound to
+    pub thread_id: Option<String>,
+    /// Whether there are more children, for dynamic variable objects
+    #[serde(rename = "has_more")]
+    pub has_more: Option<String>,
+    /// Display hint of the pretty printer
+    pub displayhint: Option<String>,
+}
+
+/// Result of the evaluation of an expression
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Evaluation {
+    /// The expression evaluated
+    pub expression: String,
+    /// The value of the expression
+    pub value: String,
+    /// The type of the expression
+    pub r#type: Option<String>,
+}
+
+/// A page of the children of a variable object
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VarChildren {
+    /// Number of children returned
+    #[serde_as(as = "DisplayFromStr")]
+    pub numchild: usize,
+    /// The children, absent if there are none
+    #[serde(default)]
+    pub children: Vec<VarObject>,
+    /// Whether there are more children after the requested range
+    pub has_more: Option<String>,
+}
+
+/// A variable object changed since the last -var-update
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VarChange {
+    /// Name of the variable object
+    pub name: String,
+    /// New value
+    pub value: Option<String>,
+    /// "true", "false" if the variable object went out of scope, or "invalid"
+    pub in_scope: String,
+    /// Whether the type has changed
+    pub type_changed: String,
+    /// New type, only present if the type has changed
+    pub new_type: Option<String>,
+    /// New number of children, only present if the type has changed
+    pub new_num_children: Option<String>,
+    /// Whether there are more children, for dynamic variable objects
+    pub has_more: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub enum RegisterRaw {
     U32(Address32),
     U64(Address64),
     U128(Address128),
     U256(Address128, Address128),
+    /// Value in another format than hex, or unavailable
+    Text(String),
 }
 
 // Define Register struct to hold register data
@@ -286,6 +798,19 @@ pub struct Register {
     pub error: Option<String>,
 }
 
+/// A register changed since the previous stop
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct RegisterChange {
+    pub name: Option<String>,
+    pub number: usize,
+    /// Value in hex at the previous stop the registers were read, absent on the
+    /// first read
+    pub previous: Option<RegisterRaw>,
+    /// Value in the requested format
+    pub value: Option<RegisterRaw>,
+}
+
 // impl Register {
 //     /// Value is not set to anything readable
 //     pub fn is_set(&self) -> bool {
@@ -327,11 +852,92 @@ impl<'de> Deserialize<'de> for RegisterRaw {
         D: serde::Deserializer<'de>,
     {
         let s: String = serde::Deserialize::deserialize(deserializer)?;
-        if s.starts_with("0x") {
-            Ok(RegisterRaw::U64(Address64::from(s[2..].to_owned())))
+        if let Some(hex) = s.strip_prefix("0x")
+            && !hex.is_empty()
+            && hex.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            Ok(RegisterRaw::U64(Address64::from(hex.to_owned())))
         } else {
-            register_data(&s).map(|(_, o)| o).map_err(|e| de::Error::custom(e.to_string()))
+            match register_data(&s) {
+                Ok((_, raw)) => Ok(raw),
+                Err(_) => Ok(RegisterRaw::Text(s)),
+            }
+        }
+    }
+}
+
+/// A piece of stream output from GDB or the program being debugged
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamOutput {
+    /// Sequence number of the output, used as the cursor to read output
+    pub seq: u64,
+    /// Console, target (including the program output) or log stream
+    pub kind: StreamKind,
+    /// Output text
+    pub text: String,
+}
+
+/// A page of stream output
+#[derive(Debug, Clone, Serialize)]
+pub struct OutputPage {
+    /// Output entries in order
+    pub entries: Vec<StreamOutput>,
+    /// Cursor to read the next page from
+    pub next_cursor: u64,
+    /// Number of entries dropped before they were read, the buffer wrapped
+    /// around
+    pub dropped: u64,
+}
+
+/// Ring buffer of the stream output of a session
+#[derive(Debug)]
+pub struct OutputBuffer {
+    entries: VecDeque<StreamOutput>,
+    next_seq: u64,
+    capacity: usize,
+}
+
+impl OutputBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self { entries: VecDeque::with_capacity(capacity), next_seq: 0, capacity }
+    }
+
+    /// Append output, dropping the oldest entry if the buffer is full
+    pub fn push(&mut self, kind: StreamKind, text: String) {
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
         }
+        self.entries.push_back(StreamOutput { seq: self.next_seq, kind, text });
+        self.next_seq += 1;
+    }
+
+    /// Cursor of the next output to be pushed
+    pub fn cursor(&self) -> u64 {
+        self.next_seq
+    }
+
+    /// Read at most `limit` entries starting from `cursor`
+    pub fn read(&self, cursor: u64, limit: usize) -> OutputPage {
+        let first_seq = self.entries.front().map_or(self.next_seq, |entry| entry.seq);
+        let entries: Vec<StreamOutput> = self
+            .entries
+            .iter()
+            .skip_while(|entry| entry.seq < cursor)
+            .take(limit)
+            .cloned()
+            .collect();
+        let next_cursor = entries.last().map_or(cursor.max(first_seq), |entry| entry.seq + 1);
+        OutputPage { entries, next_cursor, dropped: first_seq.saturating_sub(cursor) }
+    }
+
+    /// Console and target text pushed since `cursor`, log output is left out
+    pub fn text_since(&self, cursor: u64) -> String {
+        self.entries
+            .iter()
+            .skip_while(|entry| entry.seq < cursor)
+            .filter(|entry| entry.kind != StreamKind::Log)
+            .map(|entry| entry.text.as_str())
+            .collect()
     }
 }
 
@@ -349,6 +955,87 @@ pub struct ASM {
     pub func_name: Option<String>,
 }
 
+impl From<&AsmInstruction> for ASM {
+    fn from(instruction: &AsmInstruction) -> Self {
+        Self {
+            address: instruction.address.0,
+            inst: instruction.inst.clone(),
+            offset: instruction.offset.unwrap_or(0),
+            func_name: instruction.func_name.clone(),
+        }
+    }
+}
+
+/// Disassembled instruction, with its source line if requested
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AsmInstruction {
+    pub address: Address64,
+    /// Function containing the instruction
+    pub func_name: Option<String>,
+    /// Offset from the start of the function
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub offset: Option<u64>,
+    /// The instruction
+    pub inst: String,
+    /// Raw opcodes, only present if requested
+    pub opcodes: Option<String>,
+    /// Source file, only present in mixed source and disassembly
+    pub file: Option<String>,
+    /// Source line, only present in mixed source and disassembly
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub line: Option<u32>,
+}
+
+/// Result of the disassemble tool
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Disassembly {
+    /// The program counter of the selected frame, if the program is running
+    pub pc: Option<Address64>,
+    pub instructions: Vec<AsmInstruction>,
+}
+
+/// A source line with its instructions, as reported in mixed source and
+/// disassembly
+#[serde_as]
+#[derive(Deserialize)]
+struct SourceAndAsmLine {
+    #[serde_as(as = "DisplayFromStr")]
+    line: u32,
+    file: String,
+    line_asm_insn: Vec<AsmInstruction>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AsmLine {
+    Source(SourceAndAsmLine),
+    Instruction(AsmInstruction),
+}
+
+/// Parse the `asm_insns` of -data-disassemble, in any of the disassembly modes
+pub fn parse_asm_insns(value: serde_json::Value) -> Result<Vec<AsmInstruction>, serde_json::Error> {
+    let lines: Vec<AsmLine> = serde_json::from_value(value)?;
+    Ok(lines
+        .into_iter()
+        .flat_map(|line| match line {
+            AsmLine::Instruction(instruction) => vec![instruction],
+            AsmLine::Source(source) => source
+                .line_asm_insn
+                .into_iter()
+                .map(|instruction| AsmInstruction {
+                    file: Some(source.file.clone()),
+                    line: Some(source.line),
+                    ..instruction
+                })
+                .collect(),
+        })
+        .collect())
+}
+
 #[derive(Debug, Clone)]
 pub struct TrackedRegister {
     pub register: Option<Register>,
@@ -383,11 +1070,16 @@ pub struct Memory {
     pub contents: String,
 }
 
-#[derive(Debug, Clone)]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
 pub struct MemoryMapping {
+    #[serde(serialize_with = "serialize_hex")]
     pub start_address: u64,
+    #[serde(serialize_with = "serialize_hex")]
     pub end_address: u64,
+    #[serde(serialize_with = "serialize_hex")]
     pub size: u64,
+    #[serde(serialize_with = "serialize_hex")]
     pub offset: u64,
     pub permissions: Option<String>,
     pub path: Option<PathBuf>,
@@ -428,7 +1120,10 @@ impl MemoryMapping {
     /// Parse from `MEMORY_MAP_START_STR_NEW`
     fn from_str_new(line: &str) -> Result<Self, String> {
         let parts: Vec<&str> = line.split_whitespace().collect();
-        if let Some([start_address, end_address, size, offset, permissions]) = parts.first_chunk() {
+        // The path is optional, match the longer pattern first
+        if let Some([start_address, end_address, size, offset, permissions, path]) =
+            parts.first_chunk()
+        {
             Ok(MemoryMapping {
                 start_address: u64::from_str_radix(&start_address[2..], 16)
                     .map_err(|_| "Invalid start address")?,
@@ -437,9 +1132,9 @@ impl MemoryMapping {
                 size: u64::from_str_radix(&size[2..], 16).map_err(|_| "Invalid size")?,
                 offset: u64::from_str_radix(&offset[2..], 16).map_err(|_| "Invalid offset")?,
                 permissions: Some(permissions.to_string()),
-                path: None,
+                path: Some(PathBuf::from(path)),
             })
-        } else if let Some([start_address, end_address, size, offset, permissions, path]) =
+        } else if let Some([start_address, end_address, size, offset, permissions]) =
             parts.first_chunk()
         {
             Ok(MemoryMapping {
@@ -450,7 +1145,7 @@ impl MemoryMapping {
                 size: u64::from_str_radix(&size[2..], 16).map_err(|_| "Invalid size")?,
                 offset: u64::from_str_radix(&offset[2..], 16).map_err(|_| "Invalid offset")?,
                 permissions: Some(permissions.to_string()),
-                path: Some(PathBuf::from(path)),
+                path: None,
             })
         } else {
             return Err(format!("Invalid line format: {}", line));
@@ -487,7 +1182,77 @@ pub fn parse_memory_mappings_old(input: &str) -> Vec<MemoryMapping> {
     input.lines().skip(1).filter_map(|line| MemoryMapping::from_str_old(line).ok()).collect()
 }
 
-#[derive(Debug, Clone)]
+/// Parse the output of `info proc mappings`, the permissions column is only
+/// printed by recent GDB versions
+pub fn parse_memory_mappings(input: &str) -> Vec<MemoryMapping> {
+    let Some(start) = input.find("Start Addr") else {
+        return vec![];
+    };
+    let input = &input[start..];
+    if input.lines().next().is_some_and(|header| header.contains("Perms")) {
+        parse_memory_mappings_new(input)
+    } else {
+        parse_memory_mappings_old(input)
+    }
+}
+
+fn serialize_hex<S: serde::Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("0x{:x}", value))
+}
+
+/// Parse the signal reported when a core dump is loaded, e.g. "Program
+/// terminated with signal SIGSEGV, Segmentation fault.", into its name and
+/// meaning
+pub fn parse_terminating_signal(console: &str) -> Option<(String, String)> {
+    let line = console.lines().find_map(|l| l.split("Program terminated with signal ").nth(1))?;
+    let (name, meaning) = line.split_once(", ")?;
+    Some((name.to_string(), meaning.trim_end_matches('.').to_string()))
+}
+
+/// Backtrace of a thread in a core report
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ThreadBacktrace {
+    pub id: String,
+    pub target_id: String,
+    pub name: Option<String>,
+    /// Number of frames of the stack, more than the frames listed if the
+    /// backtrace was truncated
+    pub depth: usize,
+    /// Frames with their arguments and local variables
+    pub frames: Vec<StackFrame>,
+}
+
+/// Post-mortem report of a core dump
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CoreReport {
+    pub core_file: PathBuf,
+    /// Name of the signal which terminated the program, e.g. "SIGSEGV"
+    pub signal_name: Option<String>,
+    /// Description of the signal
+    pub signal_meaning: Option<String>,
+    /// Number of the signal, from the signal information saved in the core
+    pub signal_number: Option<i32>,
+    /// Address which caused the fault, for SIGSEGV, SIGBUS, SIGILL and SIGFPE
+    pub fault_address: Option<Address64>,
+    /// Mapping containing the fault address, absent if it is not mapped
+    pub fault_mapping: Option<MemoryMapping>,
+    /// The thread which received the signal
+    pub crashed_thread_id: Option<String>,
+    /// Mapping containing the program counter of the crashed thread
+    pub pc_mapping: Option<MemoryMapping>,
+    /// Registers of the crashed thread, vector registers are left out
+    pub registers: Vec<Register>,
+    /// Backtraces of all the threads
+    pub threads: Vec<ThreadBacktrace>,
+    /// Instructions around the program counter of the crashed thread
+    pub disassembly: Option<Disassembly>,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct ResolveSymbol {
     pub map: VecDeque<u64>,
     pub repeated_pattern: bool,
@@ -562,6 +1327,403 @@ mod test {
         assert_eq!(test.opt_addr, Some(Address(0xabcd1234)));
     }
 
+    #[test]
+    fn test_watchpoint() {
+        let breakpoint: BreakPoint = serde_json::from_value(serde_json::json!({
+            "number": "2",
+            "type": "hw watchpoint",
+            "disp": "keep",
+            "enabled": "y",
+            "what": "counter",
+            "times": "0"
+        }))
+        .unwrap();
+        assert!(breakpoint.address.is_none());
+        assert!(breakpoint.src_pos.is_none());
+        assert_eq!(breakpoint.what.as_deref(), Some("counter"));
+    }
+
+    #[test]
+    fn test_breakpoint_locations() {
+        let breakpoint: BreakPoint = serde_json::from_value(serde_json::json!({
+            "number": "1",
+            "type": "breakpoint",
+            "disp": "keep",
+            "enabled": "y",
+            "addr": "<MULTIPLE>",
+            "cond": "n > 1",
+            "times": "3",
+            "ignore": "2",
+            "original-location": "square",
+            "locations": [
+                {
+                    "number": "1.1",
+                    "enabled": "y",
+                    "addr": "0x0000000000401136",
+                    "func": "square<int>(int)",
+                    "file": "main.cpp",
+                    "fullname": "/tmp/main.cpp",
+                    "line": "3",
+                    "thread-groups": ["i1"]
+                },
+                {
+                    "number": "1.2",
+                    "enabled": "n",
+                    "addr": "0x0000000000401150",
+                    "func": "square<double>(double)",
+                    "thread-groups": ["i1"]
+                }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(breakpoint.cond.as_deref(), Some("n > 1"));
+        assert_eq!(breakpoint.times, Some(3));
+        assert_eq!(breakpoint.ignore, Some(2));
+        assert_eq!(breakpoint.original_location.as_deref(), Some("square"));
+        let locations = breakpoint.locations.unwrap();
+        assert_eq!(locations[0].number, BreakPointNumber { major: 1, minor: Some(1) });
+        assert_eq!(locations[0].src_pos.as_ref().unwrap().line, 3);
+        assert!(locations[1].src_pos.is_none());
+    }
+
+    #[test]
+    fn test_var_children() {
+        let children: VarChildren = serde_json::from_value(serde_json::json!({
+            "numchild": "2",
+            "children": [
+                {"name": "var1.x", "exp": "x", "numchild": "0", "value": "1", "type": "int", "thread-id": "1"},
+                {"name": "var1.next", "exp": "next", "numchild": "2", "type": "node *", "thread-id": "1"}
+            ],
+            "has_more": "0"
+        }))
+        .unwrap();
+        assert_eq!(children.numchild, 2);
+        assert_eq!(children.children[0].expression.as_deref(), Some("x"));
+        assert_eq!(children.children[1].numchild, 2);
+        assert!(children.children[1].value.is_none());
+        assert_eq!(PrintValue::AllValues.to_string(), "--all-values");
+    }
+
+    #[test]
+    fn test_asm_insns() {
+        let instructions = parse_asm_insns(serde_json::json!([
+            {"address": "0x000107c0", "func-name": "main", "offset": "4", "opcodes": "48 89 e5", "inst": "mov    %rsp,%rbp"},
+            {"address": "0x000107c4", "func-name": "main", "offset": "8", "opcodes": "c3", "inst": "ret"}
+        ]))
+        .unwrap();
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[1].address, Address(0x107c4));
+        assert_eq!(instructions[1].offset, Some(8));
+        assert!(instructions[1].line.is_none());
+
+        let instructions = parse_asm_insns(serde_json::json!([
+            {
+                "line": "31",
+                "file": "main.c",
+                "fullname": "/tmp/main.c",
+                "line_asm_insn": [
+                    {"address": "0x000107bc", "func-name": "main", "offset": "0", "inst": "push   %rbp"},
+                    {"address": "0x000107bd", "func-name": "main", "offset": "1", "inst": "mov    %rsp,%rbp"}
+                ]
+            },
+            {"line": "32", "file": "main.c", "line_asm_insn": []}
+        ]))
+        .unwrap();
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[1].line, Some(31));
+        assert_eq!(instructions[1].file.as_deref(), Some("main.c"));
+        let asm = ASM::from(&instructions[1]);
+        assert_eq!((asm.address, asm.offset), (0x107bd, 1));
+    }
+
+    #[test]
+    fn test_process_info() {
+        let processes: Vec<ProcessInfo> = serde_json::from_value(serde_json::json!([
+            {"id": "1", "type": "process", "description": "/sbin/init", "user": "root", "cores": ["0"]},
+            {"id": "4242", "type": "process", "description": "./server --port 8080", "user": "dev", "cores": ["2", "3"]}
+        ]))
+        .unwrap();
+        assert_eq!(processes[1].id, "4242");
+        assert_eq!(processes[1].cores.as_ref().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_signal_handling() {
+        let handling = parse_signal_handling(
+            "Signal        Stop\tPrint\tPass to program\tDescription\n\
+            SIGUSR1       No\tNo\tYes\t\tUser defined signal 1\n",
+        );
+        assert_eq!(handling.len(), 1);
+        assert_eq!(handling[0].signal, "SIGUSR1");
+        assert!(!handling[0].stop && !handling[0].print && handling[0].pass);
+        assert_eq!(handling[0].description, "User defined signal 1");
+    }
+
+    #[test]
+    fn test_record_status() {
+        let status = parse_record_status(
+            "Active record target: record-full\nReplay mode:\n\
+            Lowest recorded instruction number is 1.\n\
+            Current instruction number is 120.\n\
+            Highest recorded instruction number is 152.\n",
+        );
+        assert_eq!(status.target.as_deref(), Some("record-full"));
+        assert!(status.replaying);
+
+        let status = parse_record_status(
+            "Active record target: record-btrace\nRecording format: Intel Processor Trace.\n\
+            Recorded 1234 instructions in 12 functions (0 gaps) for thread 1 (process 42).\n",
+        );
+        assert_eq!(status.target.as_deref(), Some("record-btrace"));
+        assert!(!status.replaying);
+
+        let status = parse_record_status(
+            "Active record target: record-btrace\nReplay in progress.  At instruction 42.\n",
+        );
+        assert!(status.replaying);
+
+        let status = parse_record_status("No recording is currently active.\n");
+        assert!(status.target.is_none());
+        assert!(!status.replaying);
+    }
+
+    #[test]
+    fn test_register_groups() {
+        let groups = parse_register_groups(
+            " Name         Nr  Rel Offset  Size Type            Groups\n \
+            rax           0    0      0     8 int64_t         general,all,save,restore\n \
+            st0          24   24    200    10 i387_ext        float,all,save,restore\n \
+            ''          154  154    800     0 int0_t          \n \
+            eax         170  170    800     4 int32_t         general\n \
+            k0          171  171    804     8                 vector,all\n \
+            fs_base     172  172    812     8 a_very_long_type_t system\n \
+            orig_rax    173  173    820     8 long            \n",
+        );
+        assert_eq!(groups.len(), 6);
+        assert_eq!(groups[&0], ["general", "all", "save", "restore"]);
+        assert_eq!(groups[&170], ["general"]);
+        assert_eq!(groups[&171], ["vector", "all"]);
+        assert_eq!(groups[&172], ["system"]);
+        assert!(groups[&173].is_empty());
+
+        assert_eq!(
+            parse_info_registers(
+                "rax            0x1c                28\n\
+                ymm0           {v16_bfloat16 = {0x0, 0x0},\n  v8_float = {0x0, 0x0}}\n"
+            ),
+            ["rax", "ymm0"]
+        );
+
+        assert_eq!(
+            parse_architecture(
+                "The target architecture is set to \"auto\" (currently \"i386:x86-64\").\n"
+            )
+            .as_deref(),
+            Some("i386:x86-64")
+        );
+        assert_eq!(
+            parse_architecture("The target architecture is set to \"aarch64\".\n").as_deref(),
+            Some("aarch64")
+        );
+    }
+
+    #[test]
+    fn test_core_mappings() {
+        let output = "process 4242\nMapped address spaces:\n\n\
+            Start Addr           End Addr       Size     Offset  Perms  objfile\n\
+            0x555555554000     0x555555555000     0x1000        0x0  r--p   /tmp/crash\n\
+            0x555555555000     0x555555556000     0x1000     0x1000  r-xp   /tmp/crash\n\
+            0x7ffffffde000     0x7ffffffff000    0x21000        0x0  rw-p   [stack]\n";
+        let mappings = parse_memory_mappings(output);
+        assert_eq!(mappings.len(), 3);
+        let mapping = mappings.iter().find(|m| m.contains(0x555555555123)).unwrap();
+        assert!(mapping.is_exec());
+        assert!(mapping.is_path(Path::new("crash")));
+        assert!(mappings[2].is_stack());
+        assert_eq!(
+            serde_json::to_value(mapping).unwrap(),
+            serde_json::json!({
+                "start_address": "0x555555555000",
+                "end_address": "0x555555556000",
+                "size": "0x1000",
+                "offset": "0x1000",
+                "permissions": "r-xp",
+                "path": "/tmp/crash"
+            })
+        );
+
+        let output = "Mapped address spaces:\n\n\
+            Start Addr           End Addr       Size     Offset objfile\n\
+            0x400000           0x401000     0x1000        0x0 /tmp/crash\n";
+        let mappings = parse_memory_mappings(output);
+        assert_eq!(mappings.len(), 1);
+        assert!(mappings[0].permissions.is_none());
+        assert!(parse_memory_mappings("No current process").is_empty());
+
+        let console = "Core was generated by `/tmp/crash'.\n\
+            Program terminated with signal SIGSEGV, Segmentation fault.\n\
+            #0  0x0000555555555131 in main () at crash.c:4\n";
+        assert_eq!(
+            parse_terminating_signal(console),
+            Some(("SIGSEGV".to_string(), "Segmentation fault".to_string()))
+        );
+        assert_eq!(parse_terminating_signal("#0  main () at crash.c:4\n"), None);
+    }
+
+    #[test]
+    fn test_thread_list() {
+        let threads: ThreadList = serde_json::from_value(serde_json::json!({
+            "threads": [
+                {
+                    "id": "2",
+                    "target-id": "Thread 0x7ffff7a4f640 (LWP 1235)",
+                    "name": "worker",
+                    "state": "running",
+                    "core": "3"
+                },
+                {
+                    "id": "1",
+                    "target-id": "Thread 0x7ffff7d89740 (LWP 1234)",
+                    "frame": {
+                        "level": "0",
+                        "addr": "0x0000555555555189",
+                        "func": "main",
+                        "args": [],
+                        "file": "main.c",
+                        "fullname": "/tmp/main.c",
+                        "line": "12",
+                        "arch": "i386:x86-64"
+                    },
+                    "state": "stopped",
+                    "core": "1"
+                }
+            ],
+            "current-thread-id": "1"
+        }))
+        .unwrap();
+        assert_eq!(threads.current_thread_id.as_deref(), Some("1"));
+        assert_eq!(threads.threads[0].name.as_deref(), Some("worker"));
+        assert!(threads.threads[0].frame.is_none());
+        assert_eq!(threads.threads[1].frame.as_ref().unwrap().line, Some(12));
+    }
+
+    #[test]
+    fn test_stop_event() {
+        let event: StopEvent = serde_json::from_value(serde_json::json!({
+            "reason": "breakpoint-hit",
+            "disp": "keep",
+            "bkptno": "1",
+            "frame": {
+                "addr": "0x000055555557003f",
+                "func": "main",
+                "args": [],
+                "file": "main.cpp",
+                "fullname": "/tmp/main.cpp",
+                "line": "5",
+                "arch": "i386:x86-64"
+            },
+            "thread-id": "1",
+            "stopped-threads": "all",
+            "core": "6"
+        }))
+        .unwrap();
+        assert_eq!(event.reason, Some(StopReason::BreakpointHit));
+        assert_eq!(event.breakpoint.as_deref(), Some("1"));
+        assert_eq!(event.thread_id.as_deref(), Some("1"));
+        let frame = event.frame.unwrap();
+        assert_eq!(frame.level, 0);
+        assert_eq!(frame.line, Some(5));
+
+        let event: StopEvent = serde_json::from_value(serde_json::json!({
+            "reason": "read-watchpoint-trigger",
+            "hw-rwpt": {"number": "2", "exp": "counter"},
+            "value": {"value": "3"},
+            "thread-id": "1"
+        }))
+        .unwrap();
+        assert_eq!(event.reason, Some(StopReason::ReadWatchpointTrigger));
+        assert_eq!(event.watchpoint.unwrap().expression, "counter");
+        assert_eq!(event.value.unwrap().value.as_deref(), Some("3"));
+
+        let event: StopEvent = serde_json::from_value(serde_json::json!({
+            "reason": "function-finished",
+            "frame": {"addr": "0x0000555555555171", "func": "main", "args": []},
+            "gdb-result-var": "$1",
+            "return-value": "42",
+            "thread-id": "1"
+        }))
+        .unwrap();
+        assert_eq!(event.reason, Some(StopReason::FunctionFinished));
+        assert_eq!(event.return_value.as_deref(), Some("42"));
+        assert_eq!(event.gdb_result_var.as_deref(), Some("$1"));
+
+        let event: StopEvent = serde_json::from_value(serde_json::json!({
+            "reason": "syscall-entry",
+            "disp": "keep",
+            "bkptno": "3",
+            "syscall-number": "1",
+            "syscall-name": "write",
+            "thread-id": "1"
+        }))
+        .unwrap();
+        assert_eq!(event.reason, Some(StopReason::SyscallEntry));
+        assert_eq!(event.syscall_name.as_deref(), Some("write"));
+
+        let event: StopEvent = serde_json::from_value(serde_json::json!({
+            "reason": "fork",
+            "disp": "keep",
+            "bkptno": "4",
+            "newpid": "4243"
+        }))
+        .unwrap();
+        assert_eq!(event.new_pid.as_deref(), Some("4243"));
+
+        let mut event: StopEvent =
+            serde_json::from_value(serde_json::json!({"thread-id": "1"})).unwrap();
+        event.output =
+            Some("\nCatchpoint 5 (signal SIGUSR1), raise () at raise.c:50\n".to_string());
+        event.decode_signal_catchpoint();
+        assert_eq!(event.reason, Some(StopReason::SignalReceived));
+        assert_eq!(event.breakpoint.as_deref(), Some("5"));
+        assert_eq!(event.signal_name.as_deref(), Some("SIGUSR1"));
+
+        assert_eq!(
+            parse_catchpoint("Temporary catchpoint 2 (syscall 'write' [1])\n"),
+            Some(("2".to_string(), "syscall 'write' [1]".to_string()))
+        );
+        assert_eq!(parse_catchpoint("Breakpoint 1 at 0x1139: file main.c, line 3.\n"), None);
+
+        let event: StopEvent =
+            serde_json::from_value(serde_json::json!({"reason": "some-new-reason"})).unwrap();
+        assert_eq!(event.reason, Some(StopReason::Unknown));
+    }
+
+    #[test]
+    fn test_output_buffer() {
+        let mut buffer = OutputBuffer::new(3);
+        buffer.push(StreamKind::Console, "a\n".to_string());
+        buffer.push(StreamKind::Log, "b\n".to_string());
+        let cursor = buffer.cursor();
+        buffer.push(StreamKind::Target, "c\n".to_string());
+        buffer.push(StreamKind::Console, "d\n".to_string());
+        assert_eq!(buffer.text_since(cursor), "c\nd\n");
+
+        // the first entry is dropped
+        let page = buffer.read(0, 2);
+        assert_eq!(page.dropped, 1);
+        assert_eq!(page.entries.iter().map(|e| e.seq).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(page.next_cursor, 3);
+
+        let page = buffer.read(page.next_cursor, 10);
+        assert_eq!(page.entries.len(), 1);
+        assert_eq!(page.next_cursor, 4);
+
+        let page = buffer.read(page.next_cursor, 10);
+        assert!(page.entries.is_empty());
+        assert_eq!(page.next_cursor, 4);
+        assert_eq!(page.dropped, 0);
+    }
+
     #[test]
     fn test_register_normal_value() {
         #[derive(Deserialize)]
@@ -571,6 +1733,16 @@ mod test {
         let test: Test =
             serde_json::from_str("{\"reg\":{\"number\": \"1\", \"value\": \"0x1234\"}}").unwrap();
         assert_eq!(test.reg.number, 1);
+
+        // Other formats than hex are kept as text
+        let test: Test = serde_json::from_str(
+            "{\"reg\":{\"number\": \"16\", \"value\": \"0x401136 <main+4>\"}}",
+        )
+        .unwrap();
+        assert_eq!(test.reg.value, Some(RegisterRaw::Text("0x401136 <main+4>".to_string())));
+        let test: Test =
+            serde_json::from_str("{\"reg\":{\"number\": \"0\", \"value\": \"42\"}}").unwrap();
+        assert_eq!(test.reg.value, Some(RegisterRaw::Text("42".to_string())));
     }
 
     #[test]
@@ -6,9 +6,16 @@ use anyhow::Result;
 use mcp_core::tool_text_content;
 use mcp_core::types::ToolResponseContent;
 use mcp_core_macros::tool;
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaGenerator;
+use schemars::schema::Schema;
+use serde::Deserialize;
 
-use crate::gdb::GDBManager;
+use crate::error::AppError;
+use crate::gdb::{DisassembleLocation, GDBManager, collect_console};
 use crate::mi::GDB;
+use crate::mi::commands::{BreakPointLocation, BreakPointOptions, RegisterFormat};
+use crate::models::{ASM, RemoteTarget, ResolveSymbol, TrackedRegister};
 
 pub static GDB_MANAGER: LazyLock<Arc<GDBManager>> =
     LazyLock::new(|| Arc::new(GDBManager::default()));
@@ -17,6 +24,40 @@ pub fn init_gdb_manager() {
     LazyLock::force(&GDB_MANAGER);
 }
 
+/// Object parameter of a tool, its schema is inlined as the schema of a tool
+/// doesn't keep the definitions of the schemas it refers to
+#[derive(Deserialize)]
+#[serde(transparent)]
+pub struct Inline<T>(pub T);
+
+impl<T: JsonSchema> JsonSchema for Inline<T> {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        T::schema_name()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        T::json_schema(generator)
+    }
+}
+
+/// Run the commands of a tool and return its result text followed by the
+/// console text GDB printed for them, e.g. notes about breakpoint locations
+async fn with_console(
+    commands: impl Future<Output = Result<String>>,
+) -> Result<ToolResponseContent> {
+    let (text, console) = collect_console(commands).await;
+    let text = text?;
+    if console.is_empty() {
+        Ok(tool_text_content!(text))
+    } else {
+        Ok(tool_text_content!(format!("{}\nConsole: {}", text, console.trim_end())))
+    }
+}
+
 #[tool(
     name = "create_session",
     description = "Create a new GDB debugging session with optional parameters,\
@@ -36,6 +77,7 @@ pub fn init_gdb_manager() {
         args = "if provided, arguments to be passed to the inferior program",
         tty = "if provided, use TTY for input/output by the program being debugged",
         gdb_path = "if provided, path to the GDB executable",
+        remote = "if provided, connect to a remote target, e.g. a gdbserver or a QEMU gdbstub",
     )
 )]
 pub async fn create_session_tool(
@@ -53,6 +95,7 @@ pub async fn create_session_tool(
     args: Option<Vec<OsString>>,
     tty: Option<PathBuf>,
     gdb_path: Option<PathBuf>,
+    remote: Option<Inline<RemoteTarget>>,
 ) -> Result<ToolResponseContent> {
     let session = GDB_MANAGER
         .create_session(
@@ -70,6 +113,7 @@ pub async fn create_session_tool(
             args,
             tty,
             gdb_path,
+            remote.map(|remote| remote.0),
         )
         .await?;
     Ok(tool_text_content!(format!("Created GDB session: {}", session)))
@@ -101,24 +145,290 @@ pub async fn close_session_tool(session_id: String) -> Result<ToolResponseConten
     Ok(tool_text_content!("Closed GDB session".to_string()))
 }
 
+#[tool(
+    name = "read_output",
+    description = "Read the console, target and log stream output of GDB and the program \
+        being debugged. Output is kept in a ring buffer per session, pass the returned \
+        next_cursor to read the following page, dropped counts the entries lost before \
+        they were read",
+    params(
+        session_id = "The ID of the GDB session",
+        cursor = "The cursor to read from, defaults to the oldest output kept",
+        limit = "The maximum number of entries to read, defaults to 100"
+    )
+)]
+pub async fn read_output_tool(
+    session_id: String,
+    cursor: Option<u64>,
+    limit: Option<usize>,
+) -> Result<ToolResponseContent> {
+    let page = GDB_MANAGER.read_output(&session_id, cursor, limit).await?;
+    Ok(tool_text_content!(format!("Output: {}", serde_json::to_string(&page)?)))
+}
+
+#[tool(
+    name = "execute_cli",
+    description = "Execute a GDB CLI command, e.g. 'info sharedlibrary', 'x/20i $pc', \
+        'ptype struct foo' or 'info proc mappings', and return its console output",
+    params(session_id = "The ID of the GDB session", command = "The CLI command to execute")
+)]
+pub async fn execute_cli_tool(session_id: String, command: String) -> Result<ToolResponseContent> {
+    let output = GDB_MANAGER.execute_cli(&session_id, &command).await?;
+    Ok(tool_text_content!(format!("Output: {}", output)))
+}
+
+#[tool(
+    name = "execute_mi",
+    description = "Execute an arbitrary GDB/MI command, e.g. operation \
+        'data-list-changed-registers' or 'symbol-info-functions' with options ['--name', 'main'], \
+        and return the result class (done, running, connected, error or exit) and the results \
+        as JSON",
+    params(
+        session_id = "The ID of the GDB session",
+        operation = "The MI operation, with or without the leading dash",
+        options = "The array of the options, quoted using the C convention if they contain spaces",
+        parameters = "The array of the parameters, written after the options separated by '--'"
+    )
+)]
+pub async fn execute_mi_tool(
+    session_id: String,
+    operation: String,
+    options: Option<Vec<String>>,
+    parameters: Option<Vec<String>>,
+) -> Result<ToolResponseContent> {
+    let result = GDB_MANAGER.execute_mi(&session_id, &operation, options, parameters).await?;
+    Ok(tool_text_content!(format!("Result: {}", serde_json::to_string(&result)?)))
+}
+
 #[tool(
     name = "start_debugging",
-    description = "Start debugging in a session",
-    params(session_id = "The ID of the GDB session")
+    description = "Start debugging in a session and return the stop event, see continue_execution",
+    params(
+        session_id = "The ID of the GDB session",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
 )]
-pub async fn start_debugging_tool(session_id: String) -> Result<ToolResponseContent> {
-    let ret = GDB_MANAGER.start_debugging(&session_id).await?;
-    Ok(tool_text_content!(format!("Started debugging: {}", ret)))
+pub async fn start_debugging_tool(
+    session_id: String,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.start_debugging(&session_id, timeout).await?;
+        Ok(format!("Started debugging: {}", serde_json::to_string(&event)?))
+    })
+    .await
 }
 
 #[tool(
     name = "stop_debugging",
-    description = "Stop debugging in a session",
+    description = "Interrupt the running program and return the stop event, see \
+        continue_execution. GDB itself is interrupted if the program doesn't stop within the timeout",
+    params(
+        session_id = "The ID of the GDB session",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
+)]
+pub async fn stop_debugging_tool(
+    session_id: String,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.stop_debugging(&session_id, timeout).await?;
+        Ok(format!("Stopped debugging: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "handle_signal",
+    description = "Configure whether the program stops, GDB prints a message and the signal is \
+        passed to the program when it receives a signal, and return how the signal is handled. \
+        Without any option, only return how the signal is handled",
+    params(
+        session_id = "The ID of the GDB session",
+        signal = "The signal, e.g. 'SIGUSR1', a range such as '14-15' or 'all'",
+        stop = "if provided, whether the program stops, stopping implies printing",
+        print = "if provided, whether GDB prints a message, not printing implies not stopping",
+        pass = "if provided, whether the signal is passed to the program"
+    )
+)]
+pub async fn handle_signal_tool(
+    session_id: String,
+    signal: String,
+    stop: Option<bool>,
+    print: Option<bool>,
+    pass: Option<bool>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let actions = [
+            stop.map(|s| if s { "stop" } else { "nostop" }),
+            print.map(|p| if p { "print" } else { "noprint" }),
+            pass.map(|p| if p { "pass" } else { "nopass" }),
+        ];
+        let actions = actions.into_iter().flatten().collect::<Vec<_>>();
+        let handling = GDB_MANAGER.handle_signal(&session_id, &signal, &actions).await?;
+        Ok(format!("Signal handling: {}", serde_json::to_string(&handling)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "send_signal",
+    description = "Resume the program with a signal and return the stop event, see \
+        continue_execution",
+    params(
+        session_id = "The ID of the GDB session",
+        signal = "The signal, e.g. 'SIGUSR1', or '0' to resume without any signal",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
+)]
+pub async fn send_signal_tool(
+    session_id: String,
+    signal: String,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.send_signal(&session_id, &signal, timeout).await?;
+        Ok(format!("Signal sent: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "queue_signal",
+    description = "Queue a signal delivered to the current thread when the program resumes, \
+        without resuming it. The signal is passed to the program even if it is set to nopass",
+    params(session_id = "The ID of the GDB session", signal = "The signal, e.g. 'SIGUSR1'")
+)]
+pub async fn queue_signal_tool(session_id: String, signal: String) -> Result<ToolResponseContent> {
+    with_console(async {
+        GDB_MANAGER.queue_signal(&session_id, &signal).await?;
+        Ok("Signal queued".to_string())
+    })
+    .await
+}
+
+#[tool(
+    name = "connect_remote",
+    description = "Connect to a remote target, e.g. a gdbserver or a QEMU gdbstub, and return \
+        the stop event of the program if it is reported",
+    params(
+        session_id = "The ID of the GDB session",
+        target = "The target, host:port or a serial device, e.g. 'localhost:1234' or \
+            '/dev/ttyUSB0'",
+        extended = "if provided, whether to connect in extended remote mode, where programs \
+            can be run and attached to, defaults to false",
+        timeout = "if provided, the timeout in seconds to wait for the stop event"
+    )
+)]
+pub async fn connect_remote_tool(
+    session_id: String,
+    target: String,
+    extended: Option<bool>,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let target = RemoteTarget { address: target, extended: extended.unwrap_or(false) };
+        let event = GDB_MANAGER.connect_remote(&session_id, &target, timeout).await?;
+        Ok(format!("Connected to remote target: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "put_remote_file",
+    description = "Copy a file from the host to the remote target",
+    params(
+        session_id = "The ID of the GDB session",
+        host_file = "The path of the file on the host",
+        target_file = "The path of the file on the remote target"
+    )
+)]
+pub async fn put_remote_file_tool(
+    session_id: String,
+    host_file: String,
+    target_file: String,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        GDB_MANAGER.put_remote_file(&session_id, &PathBuf::from(host_file), &target_file).await?;
+        Ok("File copied to the remote target".to_string())
+    })
+    .await
+}
+
+#[tool(
+    name = "get_remote_file",
+    description = "Copy a file from the remote target to the host",
+    params(
+        session_id = "The ID of the GDB session",
+        target_file = "The path of the file on the remote target",
+        host_file = "The path of the file on the host"
+    )
+)]
+pub async fn get_remote_file_tool(
+    session_id: String,
+    target_file: String,
+    host_file: String,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        GDB_MANAGER.get_remote_file(&session_id, &target_file, &PathBuf::from(host_file)).await?;
+        Ok("File copied from the remote target".to_string())
+    })
+    .await
+}
+
+#[tool(
+    name = "attach_process",
+    description = "Attach to a running process, wait until it stops and return the stop event",
+    params(
+        session_id = "The ID of the GDB session",
+        pid = "The ID of the process",
+        timeout = "if provided, the timeout in seconds to wait for the process to stop"
+    )
+)]
+pub async fn attach_process_tool(
+    session_id: String,
+    pid: u32,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.attach_process(&session_id, pid, timeout).await?;
+        Ok(format!("Attached to process: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "detach_process",
+    description = "Detach from the process the session is attached to, the process keeps running",
     params(session_id = "The ID of the GDB session")
 )]
-pub async fn stop_debugging_tool(session_id: String) -> Result<ToolResponseContent> {
-    let ret = GDB_MANAGER.stop_debugging(&session_id).await?;
-    Ok(tool_text_content!(format!("Stopped debugging: {}", ret)))
+pub async fn detach_process_tool(session_id: String) -> Result<ToolResponseContent> {
+    with_console(async {
+        GDB_MANAGER.detach_process(&session_id).await?;
+        Ok("Detached from process".to_string())
+    })
+    .await
+}
+
+#[tool(
+    name = "list_processes",
+    description = "List the processes available to attach to, with their ID, command line, \
+        user and cores",
+    params(
+        session_id = "The ID of the GDB session",
+        filter = "if provided, only list the processes whose command line contains it"
+    )
+)]
+pub async fn list_processes_tool(
+    session_id: String,
+    filter: Option<String>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let processes = GDB_MANAGER.list_processes(&session_id, filter.as_deref()).await?;
+        Ok(format!("Processes: {}", serde_json::to_string(&processes)?))
+    })
+    .await
 }
 
 #[tool(
@@ -127,26 +437,123 @@ pub async fn stop_debugging_tool(session_id: String) -> Result<ToolResponseConte
     params(session_id = "The ID of the GDB session")
 )]
 pub async fn get_breakpoints_tool(session_id: String) -> Result<ToolResponseContent> {
-    let breakpoints = GDB_MANAGER.get_breakpoints(&session_id).await?;
-    Ok(tool_text_content!(format!("Breakpoints: {}", serde_json::to_string(&breakpoints)?)))
+    with_console(async {
+        let breakpoints = GDB_MANAGER.get_breakpoints(&session_id).await?;
+        Ok(format!("Breakpoints: {}", serde_json::to_string(&breakpoints)?))
+    })
+    .await
 }
 
 #[tool(
     name = "set_breakpoint",
-    description = "Set a breakpoint in the code",
+    description = "Set a breakpoint in the code, at a location given by either an explicit \
+        location, an address, a function (optionally in a file) or a file and line",
     params(
         session_id = "The ID of the GDB session",
-        file = "Source file path",
-        line = "Line number"
+        file = "Source file path, used with line or function",
+        line = "Line number",
+        function = "Function name",
+        address = "Address or address expression, e.g. '0x401136' or '*main+4'",
+        location = "Location passed to GDB as is, e.g. 'file.c:func' or \
+            '--source file.c --line 10', takes precedence over the other location parameters",
+        options = "if provided, the condition, ignore count and thread of the breakpoint, and \
+            whether it is temporary, hardware, disabled or pending",
     )
 )]
 pub async fn set_breakpoint_tool(
     session_id: String,
-    file: String,
-    line: usize,
+    file: Option<String>,
+    line: Option<usize>,
+    function: Option<String>,
+    address: Option<String>,
+    location: Option<String>,
+    options: Option<Inline<BreakPointOptions>>,
 ) -> Result<ToolResponseContent> {
-    let breakpoint = GDB_MANAGER.set_breakpoint(&session_id, &PathBuf::from(file), line).await?;
-    Ok(tool_text_content!(format!("Set breakpoint: {}", serde_json::to_string(&breakpoint)?)))
+    with_console(async {
+        let file = file.map(PathBuf::from);
+        let address = address.map(|addr| addr.trim().trim_start_matches('*').to_string());
+        let location = if let Some(location) = location.as_deref() {
+            BreakPointLocation::Linespec(location)
+        } else if let Some(address) = address.as_deref() {
+            let parsed = match address.strip_prefix("0x") {
+                Some(hex) => usize::from_str_radix(hex, 16).ok(),
+                None => address.parse::<usize>().ok(),
+            };
+            match parsed {
+                Some(addr) => BreakPointLocation::Address(addr),
+                None => BreakPointLocation::Linespec(&format!("*{}", address)),
+            }
+        } else if let Some(function) = function.as_deref() {
+            BreakPointLocation::Function(file.as_deref(), function)
+        } else if let (Some(file), Some(line)) = (file.as_deref(), line) {
+            BreakPointLocation::Line(file, line)
+        } else {
+            return Err(AppError::InvalidArgument(
+                "one of location, address, function or file and line is required".to_string(),
+            )
+            .into());
+        };
+        let options = options.map(|options| options.0).unwrap_or_default();
+        let breakpoint = GDB_MANAGER.set_breakpoint(&session_id, location, &options).await?;
+        Ok(format!("Set breakpoint: {}", serde_json::to_string(&breakpoint)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "set_watchpoint",
+    description = "Set a watchpoint on an expression, the program stops when it is written, \
+        read or accessed. The stop event reports the old and new values",
+    params(
+        session_id = "The ID of the GDB session",
+        expression = "The expression to watch, e.g. 'counter', 'buf[3]' or '*(int *)0x601040'",
+        mode = "if provided, one of 'write' (default), 'read' or 'access'"
+    )
+)]
+pub async fn set_watchpoint_tool(
+    session_id: String,
+    expression: String,
+    mode: Option<String>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let mode = mode.as_deref().unwrap_or("write").parse().map_err(AppError::InvalidArgument)?;
+        let watchpoint = GDB_MANAGER.set_watchpoint(&session_id, &expression, mode).await?;
+        Ok(format!("Set watchpoint: {}", serde_json::to_string(&watchpoint)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "set_catchpoint",
+    description = "Set a catchpoint, the program stops when a C++ exception is thrown, caught \
+        or rethrown, a shared library is loaded or unloaded, a system call is entered or \
+        returns, a signal is delivered, or the program forks or calls exec. The stop event \
+        reports the syscall, signal, child process, program or libraries",
+    params(
+        session_id = "The ID of the GDB session",
+        event = "One of 'throw', 'catch', 'rethrow', 'load', 'unload', 'syscall', 'signal', \
+            'fork', 'vfork' or 'exec'",
+        argument = "if provided, for throw, catch and rethrow a regular expression matching the \
+            exception type, for load and unload a regular expression matching the library name, \
+            for syscall the names or numbers of the syscalls or 'group:NAME', for signal the \
+            names or numbers of the signals or 'all', separated by spaces",
+        temporary = "if provided, whether the catchpoint is deleted after it is hit once"
+    )
+)]
+pub async fn set_catchpoint_tool(
+    session_id: String,
+    event: String,
+    argument: Option<String>,
+    temporary: Option<bool>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = event.parse().map_err(AppError::InvalidArgument)?;
+        let catchpoint = GDB_MANAGER
+            .set_catchpoint(&session_id, event, argument.as_deref(), temporary.unwrap_or(false))
+            .await?;
+        Ok(format!("Set catchpoint: {}", serde_json::to_string(&catchpoint)?))
+    })
+    .await
 }
 
 #[tool(
@@ -161,18 +568,208 @@ pub async fn delete_breakpoint_tool(
     session_id: String,
     breakpoints: Vec<String>,
 ) -> Result<ToolResponseContent> {
-    GDB_MANAGER.delete_breakpoint(&session_id, breakpoints).await?;
-    Ok(tool_text_content!("Breakpoints deleted".to_string()))
+    with_console(async {
+        GDB_MANAGER.delete_breakpoint(&session_id, breakpoints).await?;
+        Ok("Breakpoints deleted".to_string())
+    })
+    .await
 }
 
 #[tool(
-    name = "get_stack_frames",
-    description = "Get stack frames in the current GDB session",
+    name = "enable_breakpoints",
+    description = "Enable one or more breakpoints",
+    params(
+        session_id = "The ID of the GDB session",
+        breakpoints = "The array of the breakpoint numbers to enable, e.g. '1' or '2.1' for a \
+            location of a breakpoint with multiple locations"
+    )
+)]
+pub async fn enable_breakpoints_tool(
+    session_id: String,
+    breakpoints: Vec<String>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        GDB_MANAGER.enable_breakpoints(&session_id, breakpoints).await?;
+        Ok("Breakpoints enabled".to_string())
+    })
+    .await
+}
+
+#[tool(
+    name = "disable_breakpoints",
+    description = "Disable one or more breakpoints",
+    params(
+        session_id = "The ID of the GDB session",
+        breakpoints = "The array of the breakpoint numbers to disable, e.g. '1' or '2.1' for a \
+            location of a breakpoint with multiple locations"
+    )
+)]
+pub async fn disable_breakpoints_tool(
+    session_id: String,
+    breakpoints: Vec<String>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        GDB_MANAGER.disable_breakpoints(&session_id, breakpoints).await?;
+        Ok("Breakpoints disabled".to_string())
+    })
+    .await
+}
+
+#[tool(
+    name = "set_breakpoint_condition",
+    description = "Set the condition of a breakpoint, the program only stops at the breakpoint \
+        when the condition is true",
+    params(
+        session_id = "The ID of the GDB session",
+        breakpoint = "The breakpoint number",
+        condition = "The condition, e.g. 'i == 3', if not provided the condition is removed"
+    )
+)]
+pub async fn set_breakpoint_condition_tool(
+    session_id: String,
+    breakpoint: String,
+    condition: Option<String>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        GDB_MANAGER
+            .set_breakpoint_condition(&session_id, &breakpoint, condition.as_deref())
+            .await?;
+        Ok("Breakpoint condition set".to_string())
+    })
+    .await
+}
+
+#[tool(
+    name = "set_breakpoint_ignore_count",
+    description = "Ignore the next hits of a breakpoint",
+    params(
+        session_id = "The ID of the GDB session",
+        breakpoint = "The breakpoint number",
+        count = "The number of hits to ignore, 0 to stop at the next hit"
+    )
+)]
+pub async fn set_breakpoint_ignore_count_tool(
+    session_id: String,
+    breakpoint: String,
+    count: usize,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        GDB_MANAGER.set_breakpoint_ignore_count(&session_id, &breakpoint, count).await?;
+        Ok("Breakpoint ignore count set".to_string())
+    })
+    .await
+}
+
+#[tool(
+    name = "set_breakpoint_commands",
+    description = "Set the GDB CLI commands executed when a breakpoint is hit, replacing the \
+        previous ones",
+    params(
+        session_id = "The ID of the GDB session",
+        breakpoint = "The breakpoint number",
+        commands = "The array of commands, e.g. ['print x', 'continue'], empty to clear them"
+    )
+)]
+pub async fn set_breakpoint_commands_tool(
+    session_id: String,
+    breakpoint: String,
+    commands: Vec<String>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        GDB_MANAGER.set_breakpoint_commands(&session_id, &breakpoint, commands).await?;
+        Ok("Breakpoint commands set".to_string())
+    })
+    .await
+}
+
+#[tool(
+    name = "list_threads",
+    description = "List the threads of the program with their ID, state, name, target ID, core \
+        and current frame, and the ID of the selected thread",
     params(session_id = "The ID of the GDB session")
 )]
-pub async fn get_stack_frames_tool(session_id: String) -> Result<ToolResponseContent> {
-    let frames = GDB_MANAGER.get_stack_frames(&session_id).await?;
-    Ok(tool_text_content!(format!("Stack frames: {}", serde_json::to_string(&frames)?)))
+pub async fn list_threads_tool(session_id: String) -> Result<ToolResponseContent> {
+    with_console(async {
+        let threads = GDB_MANAGER.list_threads(&session_id).await?;
+        Ok(format!("Threads: {}", serde_json::to_string(&threads)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "select_thread",
+    description = "Select the thread used by the following commands and return its current frame",
+    params(session_id = "The ID of the GDB session", thread = "The ID of the thread")
+)]
+pub async fn select_thread_tool(session_id: String, thread: usize) -> Result<ToolResponseContent> {
+    with_console(async {
+        let frame = GDB_MANAGER.select_thread(&session_id, thread).await?;
+        Ok(format!("Selected thread frame: {}", serde_json::to_string(&frame)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "get_stack_frames",
+    description = "Get stack frames in the current GDB session with their arguments, \
+        and the depth of the stack",
+    params(
+        session_id = "The ID of the GDB session",
+        thread = "if provided, the ID of the thread, defaults to the selected one",
+        low_frame = "if provided, the level of the first frame to get, defaults to 0",
+        high_frame = "if provided, the level of the last frame to get, defaults to the last frame"
+    )
+)]
+pub async fn get_stack_frames_tool(
+    session_id: String,
+    thread: Option<usize>,
+    low_frame: Option<usize>,
+    high_frame: Option<usize>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let frames =
+            GDB_MANAGER.get_stack_frames(&session_id, thread, low_frame, high_frame).await?;
+        Ok(format!("Stack frames: {}", serde_json::to_string(&frames)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "select_frame",
+    description = "Select the stack frame used by the following commands and return its \
+        information",
+    params(
+        session_id = "The ID of the GDB session",
+        frame = "The level of the frame, 0 is the topest frame"
+    )
+)]
+pub async fn select_frame_tool(session_id: String, frame: usize) -> Result<ToolResponseContent> {
+    with_console(async {
+        let frame = GDB_MANAGER.select_frame(&session_id, frame).await?;
+        Ok(format!("Selected frame: {}", serde_json::to_string(&frame)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "get_frame_info",
+    description = "Get the information of a stack frame with its arguments",
+    params(
+        session_id = "The ID of the GDB session",
+        thread = "if provided, the ID of the thread, defaults to the selected one",
+        frame = "if provided, the level of the frame, defaults to the selected one"
+    )
+)]
+pub async fn get_frame_info_tool(
+    session_id: String,
+    thread: Option<usize>,
+    frame: Option<usize>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let frame = GDB_MANAGER.get_frame_info(&session_id, thread, frame).await?;
+        Ok(format!("Frame: {}", serde_json::to_string(&frame)?))
+    })
+    .await
 }
 
 #[tool(
@@ -180,47 +777,241 @@ pub async fn get_stack_frames_tool(session_id: String) -> Result<ToolResponseCon
     description = "Get local variables in the current stack frame",
     params(
         session_id = "The ID of the GDB session",
+        thread = "if provided, the ID of the thread, defaults to the selected one",
         frame_id = "The ID of the stack frame, defaults to 0, the topest frame"
     )
 )]
 pub async fn get_local_variables_tool(
     session_id: String,
+    thread: Option<usize>,
     frame_id: Option<usize>,
 ) -> Result<ToolResponseContent> {
-    let variables = GDB_MANAGER.get_local_variables(&session_id, frame_id).await?;
-    Ok(tool_text_content!(format!("Local variables: {}", serde_json::to_string(&variables)?)))
+    with_console(async {
+        let variables = GDB_MANAGER.get_local_variables(&session_id, thread, frame_id).await?;
+        Ok(format!("Local variables: {}", serde_json::to_string(&variables)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "var_create",
+    description = "Create a variable object for an expression in the current frame, to explore \
+        structs, arrays and containers with var_list_children and track changes with var_update",
+    params(
+        session_id = "The ID of the GDB session",
+        expression = "The expression, e.g. 'config', 'argv[0]' or '*node'"
+    )
+)]
+pub async fn var_create_tool(
+    session_id: String,
+    expression: String,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let var_object = GDB_MANAGER.create_var_object(&session_id, &expression).await?;
+        Ok(format!("Variable object: {}", serde_json::to_string(&var_object)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "var_list_children",
+    description = "List the children of a variable object, e.g. the fields of a struct or the \
+        elements of an array",
+    params(
+        session_id = "The ID of the GDB session",
+        name = "The name of the variable object",
+        print_values = "if provided, whether to include the values, defaults to true",
+        from = "if provided, the index of the first child to list",
+        to = "if provided, the index after the last child to list",
+    )
+)]
+pub async fn var_list_children_tool(
+    session_id: String,
+    name: String,
+    print_values: Option<bool>,
+    from: Option<u64>,
+    to: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let children = GDB_MANAGER
+            .list_var_children(&session_id, &name, print_values.unwrap_or(true), from, to)
+            .await?;
+        Ok(format!("Children: {}", serde_json::to_string(&children)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "var_update",
+    description = "Update variable objects and return those which changed, e.g. after a step",
+    params(
+        session_id = "The ID of the GDB session",
+        name = "if provided, the variable object to update with its children, otherwise all",
+    )
+)]
+pub async fn var_update_tool(
+    session_id: String,
+    name: Option<String>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let changes = GDB_MANAGER.update_var_objects(&session_id, name.as_deref()).await?;
+        Ok(format!("Changes: {}", serde_json::to_string(&changes)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "var_assign",
+    description = "Assign the value of an expression to a variable object",
+    params(
+        session_id = "The ID of the GDB session",
+        name = "The name of the variable object",
+        expression = "The expression of the new value"
+    )
+)]
+pub async fn var_assign_tool(
+    session_id: String,
+    name: String,
+    expression: String,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let value = GDB_MANAGER.assign_var_object(&session_id, &name, &expression).await?;
+        Ok(format!("New value: {}", value))
+    })
+    .await
+}
+
+#[tool(
+    name = "var_set_format",
+    description = "Set the display format of a variable object",
+    params(
+        session_id = "The ID of the GDB session",
+        name = "The name of the variable object",
+        format = "One of 'binary', 'decimal', 'hexadecimal', 'octal', 'natural' or \
+            'zero-hexadecimal'"
+    )
+)]
+pub async fn var_set_format_tool(
+    session_id: String,
+    name: String,
+    format: String,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let format = format.parse().map_err(AppError::InvalidArgument)?;
+        let value = GDB_MANAGER.set_var_format(&session_id, &name, format).await?;
+        Ok(format!("Value: {}", value))
+    })
+    .await
+}
+
+#[tool(
+    name = "var_delete",
+    description = "Delete a variable object and its children",
+    params(session_id = "The ID of the GDB session", name = "The name of the variable object")
+)]
+pub async fn var_delete_tool(session_id: String, name: String) -> Result<ToolResponseContent> {
+    with_console(async {
+        GDB_MANAGER.delete_var_object(&session_id, &name).await?;
+        Ok("Variable object deleted".to_string())
+    })
+    .await
 }
 
 #[tool(
     name = "get_registers",
-    description = "Get registers in the current GDB session",
+    description = "Get registers in the current GDB session. With changed_only, only the registers \
+        changed since the previous stop the registers were read are returned, with their previous \
+        value in hex; on the first read all registers are returned without a previous value",
     params(
         session_id = "The ID of the GDB session",
-        reg_list = "The array of the registers to get",
+        reg_list = "if provided, the array of the registers to get, by name (e.g. 'rip', 'x0') \
+            or number, otherwise all registers",
+        group = "if provided without reg_list, only the registers of this group, e.g. general, \
+            float, vector or system, see get_register_names",
+        format = "if provided, the format of the values: hex, decimal, octal, binary, raw or \
+            natural, defaults to hex",
+        changed_only = "if provided, whether to only return the changed registers",
     )
 )]
 pub async fn get_registers_tool(
     session_id: String,
     reg_list: Option<Vec<String>>,
+    group: Option<String>,
+    format: Option<String>,
+    changed_only: Option<bool>,
 ) -> Result<ToolResponseContent> {
-    let registers = GDB_MANAGER.get_registers(&session_id, reg_list).await?;
-    Ok(tool_text_content!(format!("Registers: {}", serde_json::to_string(&registers)?)))
+    with_console(async {
+        let reg_list = match (reg_list, group) {
+            (None, Some(group)) => Some(
+                GDB_MANAGER
+                    .get_register_names(&session_id, Some(&group))
+                    .await?
+                    .into_iter()
+                    .map(|r| r.number.to_string())
+                    .collect(),
+            ),
+            (reg_list, _) => reg_list,
+        };
+        let format =
+            format.as_deref().unwrap_or("hex").parse().map_err(AppError::InvalidArgument)?;
+        if changed_only.unwrap_or(false) {
+            let changes = GDB_MANAGER.get_changed_registers(&session_id, reg_list, format).await?;
+
+            // Highlight them in the TUI
+            let mut app = crate::APP.lock().await;
+            app.register_changed = app
+                .registers
+                .iter()
+                .enumerate()
+                .filter(|(_, tracked)| {
+                    tracked
+                        .register
+                        .as_ref()
+                        .is_some_and(|r| changes.iter().any(|c| c.number == r.number))
+                })
+                .map(|(i, _)| i)
+                .collect();
+            drop(app);
+
+            return Ok(format!("Changed registers: {}", serde_json::to_string(&changes)?));
+        }
+
+        let all = reg_list.is_none();
+        let registers = GDB_MANAGER.get_registers(&session_id, reg_list, format).await?;
+
+        // Show them in the TUI, which only displays hex values
+        if all && format == RegisterFormat::Hex {
+            let mut app = crate::APP.lock().await;
+            app.registers = registers
+                .iter()
+                .map(|r| TrackedRegister::new(Some(r.clone()), ResolveSymbol::default()))
+                .collect();
+            app.register_changed.clear();
+        }
+        Ok(format!("Registers: {}", serde_json::to_string(&registers)?))
+    })
+    .await
 }
 
 #[tool(
     name = "get_register_names",
-    description = "Get register names in the current GDB session",
+    description = "Get the registers of the current architecture with their name, number and \
+        register groups",
     params(
         session_id = "The ID of the GDB session",
-        reg_list = "The array of the registers to get",
+        group = "if provided, only the registers of this group, e.g. general, float, vector, \
+            system or all",
     )
 )]
 pub async fn get_register_names_tool(
     session_id: String,
-    reg_list: Option<Vec<String>>,
+    group: Option<String>,
 ) -> Result<ToolResponseContent> {
-    let registers = GDB_MANAGER.get_register_names(&session_id, reg_list).await?;
-    Ok(tool_text_content!(format!("Registers: {}", serde_json::to_string(&registers)?)))
+    with_console(async {
+        let registers = GDB_MANAGER.get_register_names(&session_id, group.as_deref()).await?;
+        Ok(format!("Registers: {}", serde_json::to_string(&registers)?))
+    })
+    .await
 }
 
 #[tool(
@@ -257,46 +1048,549 @@ pub async fn read_memory_tool(
     count: usize,
     offset: Option<isize>,
 ) -> Result<ToolResponseContent> {
-    let memory = GDB_MANAGER.read_memory(&session_id, offset, address, count).await?;
-    Ok(tool_text_content!(format!("Memory: {}", serde_json::to_string(&memory)?)))
+    with_console(async {
+        let memory = GDB_MANAGER.read_memory(&session_id, offset, address, count).await?;
+        Ok(format!("Memory: {}", serde_json::to_string(&memory)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "write_memory",
+    description = "Write bytes to the memory of the program and return the number of bytes \
+        written. The address is checked against the memory mappings of the process when they \
+        are known, the bytes must all be in the same mapping",
+    params(
+        session_id = "The ID of the GDB session",
+        address = "An expression specifying the address to write at, e.g. '0x601040' or '&buf[2]'",
+        contents = "The bytes to write, hex encoded, e.g. '90ff00'",
+        repeat = "if provided, the number of times the contents are written one after another, \
+            at least 1"
+    )
+)]
+pub async fn write_memory_tool(
+    session_id: String,
+    address: String,
+    contents: String,
+    repeat: Option<usize>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let written = GDB_MANAGER.write_memory(&session_id, &address, &contents, repeat).await?;
+        Ok(format!("Wrote {} bytes", written))
+    })
+    .await
+}
+
+#[tool(
+    name = "set_register",
+    description = "Set a register of the selected frame to the value of an expression and \
+        return its new value",
+    params(
+        session_id = "The ID of the GDB session",
+        register = "The name or number of the register, see get_register_names",
+        value = "The expression whose value is written, e.g. '0x2a' or '$rsp + 8'"
+    )
+)]
+pub async fn set_register_tool(
+    session_id: String,
+    register: String,
+    value: String,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let register = GDB_MANAGER.set_register(&session_id, &register, &value).await?;
+        Ok(format!("Register: {}", serde_json::to_string(&register)?))
+    })
+    .await
+}
+
+/// Source lines to disassemble
+#[derive(Deserialize, JsonSchema)]
+pub struct SourceLines {
+    /// The source file
+    file: PathBuf,
+    /// The line to start from
+    line: usize,
+    /// The number of lines, otherwise the whole function containing the line
+    lines: Option<usize>,
+}
+
+/// Address range to disassemble
+#[derive(Deserialize, JsonSchema)]
+pub struct AddressRange {
+    /// The start of the range, e.g. '0x401136' or '$pc - 16'
+    start: String,
+    /// The end of the range, excluded
+    end: String,
+}
+
+#[tool(
+    name = "disassemble",
+    description = "Disassemble a function, source lines, an address range, or by default the \
+        instructions around the program counter. Each instruction has its address, function, \
+        offset, instruction text and raw opcodes, and its source file and line if source is true.",
+    params(
+        session_id = "The ID of the GDB session",
+        function = "if provided, the function to disassemble, by name or by an address in it",
+        source_lines = "if provided, the source lines to disassemble",
+        address_range = "if provided, the address range to disassemble",
+        count = "The number of instructions before and after the program counter, defaults to 10",
+        source = "if provided, whether to include the source file and line of the instructions",
+    )
+)]
+pub async fn disassemble_tool(
+    session_id: String,
+    function: Option<String>,
+    source_lines: Option<Inline<SourceLines>>,
+    address_range: Option<Inline<AddressRange>>,
+    count: Option<usize>,
+    source: Option<bool>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let location = if let Some(function) = function.as_deref() {
+            DisassembleLocation::Function(function)
+        } else if let Some(Inline(lines)) = &source_lines {
+            DisassembleLocation::File(&lines.file, lines.line, lines.lines)
+        } else if let Some(Inline(range)) = &address_range {
+            DisassembleLocation::Range(&range.start, &range.end)
+        } else {
+            DisassembleLocation::AroundPc(count.unwrap_or(10))
+        };
+        let disassembly =
+            GDB_MANAGER.disassemble(&session_id, location, source.unwrap_or(false)).await?;
+
+        // Show it in the TUI
+        let mut app = crate::APP.lock().await;
+        app.asm = disassembly.instructions.iter().map(ASM::from).collect();
+        if let Some(pc) = disassembly.pc {
+            app.current_pc = pc.0;
+        }
+        drop(app);
+        Ok(format!("Disassembly: {}", serde_json::to_string(&disassembly)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "analyze_core",
+    description = "Load a core dump and return a post-mortem report of the crash: the signal, \
+        the faulting address and the memory mapping containing it, the registers and the \
+        instructions around the program counter of the crashed thread, and the backtrace of \
+        every thread with arguments and local variables",
+    params(
+        session_id = "The ID of the GDB session",
+        core_file = "if provided, path to the core dump to load, defaults to the core file the \
+            session was created with",
+        max_frames = "if provided, the maximum number of frames of each backtrace, \
+            defaults to 32"
+    )
+)]
+pub async fn analyze_core_tool(
+    session_id: String,
+    core_file: Option<String>,
+    max_frames: Option<usize>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let report = GDB_MANAGER
+            .analyze_core(&session_id, core_file.map(PathBuf::from), max_frames.unwrap_or(32))
+            .await?;
+        Ok(format!("Core report: {}", serde_json::to_string(&report)?))
+    })
+    .await
 }
 
 #[tool(
     name = "continue_execution",
-    description = "Continue program execution",
-    params(session_id = "The ID of the GDB session")
+    description = "Continue program execution, wait until the program stops and return the stop \
+        event (reason, frame, thread, signal or exit code) with the output produced meanwhile. \
+        The other execution tools return the same stop event",
+    params(
+        session_id = "The ID of the GDB session",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
 )]
-pub async fn continue_execution_tool(session_id: String) -> Result<ToolResponseContent> {
-    let ret = GDB_MANAGER.continue_execution(&session_id).await?;
-    Ok(tool_text_content!(format!("Continued execution: {}", ret)))
+pub async fn continue_execution_tool(
+    session_id: String,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.continue_execution(&session_id, timeout).await?;
+        Ok(format!("Continued execution: {}", serde_json::to_string(&event)?))
+    })
+    .await
 }
 
 #[tool(
     name = "step_execution",
-    description = "Step into next line",
-    params(session_id = "The ID of the GDB session")
+    description = "Step into next line and return the stop event, see continue_execution",
+    params(
+        session_id = "The ID of the GDB session",
+        count = "if provided, the number of lines to step",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
 )]
-pub async fn step_execution_tool(session_id: String) -> Result<ToolResponseContent> {
-    let ret = GDB_MANAGER.step_execution(&session_id).await?;
-    Ok(tool_text_content!(format!("Stepped into next line: {}", ret)))
+pub async fn step_execution_tool(
+    session_id: String,
+    count: Option<usize>,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.step_execution(&session_id, count, timeout).await?;
+        Ok(format!("Stepped into next line: {}", serde_json::to_string(&event)?))
+    })
+    .await
 }
 
 #[tool(
     name = "next_execution",
-    description = "Step over next line",
+    description = "Step over next line and return the stop event, see continue_execution",
+    params(
+        session_id = "The ID of the GDB session",
+        count = "if provided, the number of lines to step",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
+)]
+pub async fn next_execution_tool(
+    session_id: String,
+    count: Option<usize>,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.next_execution(&session_id, count, timeout).await?;
+        Ok(format!("Stepped over next line: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "step_instruction",
+    description = "Step into next machine instruction and return the stop event, see \
+        continue_execution",
+    params(
+        session_id = "The ID of the GDB session",
+        count = "if provided, the number of instructions to step",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
+)]
+pub async fn step_instruction_tool(
+    session_id: String,
+    count: Option<usize>,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.step_instruction(&session_id, count, timeout).await?;
+        Ok(format!("Stepped into next instruction: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "next_instruction",
+    description = "Step over next machine instruction, calls included, and return the stop event, \
+        see continue_execution",
+    params(
+        session_id = "The ID of the GDB session",
+        count = "if provided, the number of instructions to step",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
+)]
+pub async fn next_instruction_tool(
+    session_id: String,
+    count: Option<usize>,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.next_instruction(&session_id, count, timeout).await?;
+        Ok(format!("Stepped over next instruction: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "finish_execution",
+    description = "Run until the current function returns and return the stop event, see \
+        continue_execution. The stop event has the returned value",
+    params(
+        session_id = "The ID of the GDB session",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
+)]
+pub async fn finish_execution_tool(
+    session_id: String,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.finish_execution(&session_id, timeout).await?;
+        Ok(format!("Finished function: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "until_execution",
+    description = "Run until a line after the current one is reached, e.g. to leave a loop, or \
+        until a location, stopping anyway when the current function returns, and return the \
+        stop event, see continue_execution",
+    params(
+        session_id = "The ID of the GDB session",
+        location = "if provided, the location to run until, e.g. a line, file:line, function or \
+            *address",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
+)]
+pub async fn until_execution_tool(
+    session_id: String,
+    location: Option<String>,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.until_execution(&session_id, location.as_deref(), timeout).await?;
+        Ok(format!("Ran until: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "advance_execution",
+    description = "Run until a location, stopping anyway when the current function returns, \
+        and return the stop event, see continue_execution",
+    params(
+        session_id = "The ID of the GDB session",
+        location = "The location to run until, e.g. a line, file:line, function or *address",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
+)]
+pub async fn advance_execution_tool(
+    session_id: String,
+    location: String,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.advance_execution(&session_id, &location, timeout).await?;
+        Ok(format!("Advanced: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "jump_execution",
+    description = "Resume the program at a location, skipping or repeating code, and return the \
+        stop event, see continue_execution",
+    params(
+        session_id = "The ID of the GDB session",
+        location = "The location to resume at, e.g. a line, file:line, function or *address",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
+)]
+pub async fn jump_execution_tool(
+    session_id: String,
+    location: String,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.jump_execution(&session_id, &location, timeout).await?;
+        Ok(format!("Jumped: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "return_from_function",
+    description = "Make the current function return immediately without executing the rest \
+        of it, and return the frame of the caller. The program doesn't run",
+    params(
+        session_id = "The ID of the GDB session",
+        value = "if provided, the expression whose value is returned"
+    )
+)]
+pub async fn return_from_function_tool(
+    session_id: String,
+    value: Option<String>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let frame = GDB_MANAGER.return_from_function(&session_id, value.as_deref()).await?;
+        Ok(format!("Returned to: {}", serde_json::to_string(&frame)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "start_recording",
+    description = "Start recording the program so that it can be executed backwards with the \
+        reverse tools. The program must be running",
+    params(
+        session_id = "The ID of the GDB session",
+        method = "if provided, the record method: full (default, records every instruction), \
+            btrace (hardware branch tracing), or bts or pt to choose the branch trace format"
+    )
+)]
+pub async fn start_recording_tool(
+    session_id: String,
+    method: Option<String>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let method =
+            method.as_deref().unwrap_or("full").parse().map_err(AppError::InvalidArgument)?;
+        GDB_MANAGER.start_recording(&session_id, method).await?;
+        Ok("Recording started".to_string())
+    })
+    .await
+}
+
+#[tool(
+    name = "stop_recording",
+    description = "Stop recording the program and delete the execution log, the program \
+        continues from the current position",
+    params(session_id = "The ID of the GDB session")
+)]
+pub async fn stop_recording_tool(session_id: String) -> Result<ToolResponseContent> {
+    with_console(async {
+        GDB_MANAGER.stop_recording(&session_id).await?;
+        Ok("Recording stopped".to_string())
+    })
+    .await
+}
+
+#[tool(
+    name = "get_record_status",
+    description = "Get the status of the process record: the record target, whether the \
+        program is replaying the execution log, and the details printed by 'info record'",
     params(session_id = "The ID of the GDB session")
 )]
-pub async fn next_execution_tool(session_id: String) -> Result<ToolResponseContent> {
-    let ret = GDB_MANAGER.next_execution(&session_id).await?;
+pub async fn get_record_status_tool(session_id: String) -> Result<ToolResponseContent> {
+    with_console(async {
+        let status = GDB_MANAGER.get_record_status(&session_id).await?;
+        Ok(format!("Record status: {}", serde_json::to_string(&status)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "reverse_step",
+    description = "Step backwards into the previous line and return the stop event, see \
+        continue_execution. The program must be recorded, see start_recording",
+    params(
+        session_id = "The ID of the GDB session",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
+)]
+pub async fn reverse_step_tool(
+    session_id: String,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.reverse_step(&session_id, timeout).await?;
+        Ok(format!("Stepped back into previous line: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "reverse_next",
+    description = "Step backwards over the previous line and return the stop event, see \
+        continue_execution. The program must be recorded, see start_recording",
+    params(
+        session_id = "The ID of the GDB session",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
+)]
+pub async fn reverse_next_tool(
+    session_id: String,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.reverse_next(&session_id, timeout).await?;
+        Ok(format!("Stepped back over previous line: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "reverse_continue",
+    description = "Continue backwards until a breakpoint, a watchpoint or the start of \
+        the execution log, and return the stop event, see continue_execution. The program \
+        must be recorded, see start_recording",
+    params(
+        session_id = "The ID of the GDB session",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
+)]
+pub async fn reverse_continue_tool(
+    session_id: String,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.reverse_continue(&session_id, timeout).await?;
+        Ok(format!("Continued backwards: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "reverse_finish",
+    description = "Go backwards to the call of the current function and return the stop event, \
+        see continue_execution. The program must be recorded, see start_recording",
+    params(
+        session_id = "The ID of the GDB session",
+        timeout = "The seconds to wait for the program to stop, defaults to the command timeout"
+    )
+)]
+pub async fn reverse_finish_tool(
+    session_id: String,
+    timeout: Option<u64>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let event = GDB_MANAGER.reverse_finish(&session_id, timeout).await?;
+        Ok(format!("Returned to caller: {}", serde_json::to_string(&event)?))
+    })
+    .await
+}
+
+#[tool(
+    name = "evaluate_expression",
+    description = "Evaluate an expression and return its value and type",
+    params(
+        session_id = "The ID of the GDB session",
+        expression = "The expression to evaluate, e.g. 'node->next', 'sizeof(buf)' or 'a + b'",
+        thread = "if provided, the ID of the thread to evaluate in, defaults to the selected one",
+        frame = "if provided, the level of the frame to evaluate in, defaults to the selected one",
+        format = "if provided, one of 'x' (hexadecimal), 'd' (decimal), 'o' (octal), \
+            't' (binary), 'z' (zero padded hexadecimal) or 'N' (natural)",
+    )
+)]
+pub async fn evaluate_expression_tool(
+    session_id: String,
+    expression: String,
+    thread: Option<usize>,
+    frame: Option<usize>,
+    format: Option<String>,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let format = format.map(|f| f.parse()).transpose().map_err(AppError::InvalidArgument)?;
+        let evaluation = GDB_MANAGER
+            .evaluate_expression(&session_id, &expression, thread, frame, format)
+            .await?;
+        Ok(format!("Evaluation: {}", serde_json::to_string(&evaluation)?))
+    })
+    .await
+}
+
 #[tool(
     name = "modify_variable",
-    description = "Modify a variable's value in the current GDB session",
+    description = "Modify a variable's value in the current GDB session and return the new value",
     params(
         session_id = "The ID of the GDB session",
-        expression = "The expression to evaluate, e.g. 'variable=value'"
+        variable = "The variable or lvalue expression to modify, e.g. 'count' or 'node->next'",
+        value = "The expression of the new value"
     )
 )]
-pub async fn modify_variable_tool(session_id: String, expression: String) -> Result<ToolResponseContent> {
-    let ret = GDB_MANAGER.modify_variable(&session_id, expression).await?;
-    Ok(tool_text_content!(format!("Modified variable: {}", ret)))
+pub async fn modify_variable_tool(
+    session_id: String,
+    variable: String,
+    value: String,
+) -> Result<ToolResponseContent> {
+    with_console(async {
+        let evaluation = GDB_MANAGER.modify_variable(&session_id, &variable, &value).await?;
+        Ok(format!("Modified variable: {}", serde_json::to_string(&evaluation)?))
+    })
+    .await
 }
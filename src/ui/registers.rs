@@ -43,7 +43,7 @@ pub fn draw_registers<'a>(app: &App, f: &mut Frame<'a>, register: Rect) {
         if let Some(reg) = register {
             if let (Some(name), Some(value)) = (&reg.name, &reg.value) {
                 if let RegisterRaw::U64(val) = value {
-                    let changed = reg_changed.contains(&(i as u8));
+                    let changed = reg_changed.contains(&i);
                     let mut reg_name =
                         Span::from(format!("  {name:width$}", width = longest_register_name))
                             .style(Style::new().fg(PURPLE));
@@ -0,0 +1,86 @@
+//! Remote debugging against a gdbserver listening on loopback. Ignored by
+//! default as it needs gdb and gdbserver, run it with `cargo test -- --ignored`
+
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+
+use anyhow::{Result, bail};
+use mcp_core::client::{Client, ClientBuilder};
+use mcp_core::transport::ClientStdioTransport;
+use mcp_core::types::{ClientCapabilities, Implementation, ToolResponseContent};
+use serde_json::{Value, json};
+
+/// Call a tool and return its text, failing if the tool reports an error
+async fn call_tool(
+    client: &Client<ClientStdioTransport>,
+    name: &str,
+    params: Value,
+) -> Result<String> {
+    let response = client.call_tool(name, Some(params)).await?;
+    let text = match response.content.first() {
+        Some(ToolResponseContent::Text { text }) => text.clone(),
+        _ => bail!("{} returned no text: {:?}", name, response.content),
+    };
+    if response.is_error == Some(true) {
+        bail!("{} failed: {}", name, text);
+    }
+    Ok(text)
+}
+
+#[tokio::test]
+#[ignore = "needs gdb and gdbserver"]
+async fn test_connect_remote_loopback() -> Result<()> {
+    let program = env!("CARGO_BIN_EXE_test_app");
+
+    // Port 0 lets gdbserver pick a free port, which it reports on stderr
+    let mut gdbserver = Command::new("gdbserver")
+        .args(["--once", "127.0.0.1:0", program])
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stderr = BufReader::new(gdbserver.stderr.take().unwrap());
+    let Some(port) = stderr.lines().map_while(|line| line.ok()).find_map(|line| {
+        line.strip_prefix("Listening on port ").and_then(|port| port.trim().parse::<u16>().ok())
+    }) else {
+        gdbserver.kill()?;
+        bail!("gdbserver didn't report its port");
+    };
+
+    let transport = ClientStdioTransport::new(env!("CARGO_BIN_EXE_mcp-server-gdb"), &[])?;
+    let client = ClientBuilder::new(transport).build();
+    client.open().await?;
+    client
+        .initialize(
+            Implementation { name: "remote-test".to_string(), version: "1.0".to_string() },
+            ClientCapabilities::default(),
+        )
+        .await?;
+
+    let text = call_tool(&client, "create_session", json!({ "program": program })).await?;
+    let session_id = text.strip_prefix("Created GDB session: ").unwrap_or(&text).to_string();
+
+    let text = call_tool(
+        &client,
+        "connect_remote",
+        json!({
+            "session_id": session_id,
+            "target": format!("127.0.0.1:{}", port),
+            "timeout": 10
+        }),
+    )
+    .await?;
+    assert!(text.starts_with("Connected to remote target: "), "{}", text);
+
+    // The program stopped at its entry point runs to the end
+    let text = call_tool(
+        &client,
+        "continue_execution",
+        json!({ "session_id": session_id, "timeout": 10 }),
+    )
+    .await?;
+    assert!(text.contains("exited-normally"), "{}", text);
+
+    call_tool(&client, "close_session", json!({ "session_id": session_id })).await?;
+    let _ = gdbserver.kill();
+    gdbserver.wait()?;
+    Ok(())
+}